@@ -3,11 +3,12 @@ use std::path::{Path, PathBuf};
 use clang::Clang;
 
 use crate::{
-    Env,
+    Env, OffsetCommentMismatch, OffsetCommentOptions,
     error::{
         AddIncludePathError, ClangInitSnafu, DoesNotExistSnafu, FileNotFoundSnafu,
-        NotADirectorySnafu, ParseError, TypeCrawlerError,
+        NotADirectorySnafu, ParseError, ReadSnafu, TypeCrawlerError,
     },
+    offset_comments::Annotation,
     parser::Parser,
     types::Types,
 };
@@ -16,6 +17,7 @@ pub struct TypeCrawler {
     clang: Clang,
     include_paths: Vec<PathBuf>,
     env: Env,
+    collect_macros: bool,
 }
 
 impl TypeCrawler {
@@ -25,7 +27,7 @@ impl TypeCrawler {
     }
 
     pub fn from_clang(clang: Clang, env: Env) -> Self {
-        TypeCrawler { clang, include_paths: Vec::new(), env }
+        TypeCrawler { clang, include_paths: Vec::new(), env, collect_macros: false }
     }
 
     pub fn add_include_path<P: AsRef<Path>>(&mut self, path: P) -> Result<(), AddIncludePathError> {
@@ -44,6 +46,13 @@ impl TypeCrawler {
         Ok(())
     }
 
+    /// Enables collecting object-like macros which evaluate to constants, see
+    /// [`Types::macro_constants`]. This is off by default, as clang then has to record every
+    /// macro definition and expansion.
+    pub fn set_collect_macros(&mut self, collect_macros: bool) {
+        self.collect_macros = collect_macros;
+    }
+
     fn arguments(&self) -> Vec<String> {
         self.include_paths
             .iter()
@@ -63,14 +72,77 @@ impl TypeCrawler {
         }
 
         let index = clang::Index::new(&self.clang, false, false);
+        let unit = self.parse_unit(&index, path, self.collect_macros)?;
+        self.crawl(&unit)
+    }
+
+    /// Compares the `/* 0x24 */` comments before each field of the records defined in a file with
+    /// the field offsets, and returns the comments which are wrong
+    pub fn check_offset_comments<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        options: &OffsetCommentOptions,
+    ) -> Result<Vec<OffsetCommentMismatch>, ParseError> {
+        let path = file_path.as_ref();
+        if !path.exists() {
+            return FileNotFoundSnafu { name: path.display().to_string() }.fail();
+        }
+
+        let index = clang::Index::new(&self.clang, false, false);
+        let unit = self.parse_unit(&index, path, false)?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|_| ReadSnafu { path: path.display().to_string() }.build())?;
+
+        Ok(Annotation::collect(&self.env, &unit.get_entity(), &source)
+            .iter()
+            .filter(|a| options.sizes || !a.is_size())
+            .filter_map(|a| a.mismatch())
+            .collect())
+    }
+
+    /// Returns the contents of a file with a `/* 0x24 */` comment before each field of the records
+    /// defined in it, inserting missing comments and correcting wrong ones. Comments with the
+    /// right value are reformatted to match the options. Nothing else in the file is changed.
+    /// Fails if two comments would overlap, rather than dropping one of them.
+    pub fn rewrite_offset_comments<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        options: &OffsetCommentOptions,
+    ) -> Result<String, ParseError> {
+        let path = file_path.as_ref();
+        if !path.exists() {
+            return FileNotFoundSnafu { name: path.display().to_string() }.fail();
+        }
+
+        let index = clang::Index::new(&self.clang, false, false);
+        let unit = self.parse_unit(&index, path, false)?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|_| ReadSnafu { path: path.display().to_string() }.build())?;
+
+        let annotations = Annotation::collect(&self.env, &unit.get_entity(), &source);
+        Annotation::rewrite(&annotations, &source, options)
+    }
+
+    fn parse_unit<'i>(
+        &self,
+        index: &'i clang::Index,
+        path: &Path,
+        collect_macros: bool,
+    ) -> Result<clang::TranslationUnit<'i>, ParseError> {
         let mut parser = index.parser(path);
         parser.arguments(&self.arguments());
-        let unit = parser.parse()?;
+        parser.detailed_preprocessing_record(collect_macros);
+        // Records declared under `#pragma pack` get an implicit attribute pointing at the pragma
+        parser.visit_implicit_attributes(true);
+        Ok(parser.parse()?)
+    }
 
+    fn crawl(&self, unit: &clang::TranslationUnit) -> Result<Types, ParseError> {
         let root = unit.get_entity();
 
         let mut context = Parser::new();
         context.parse(&self.env, &root)?;
+        context.evaluate_macros(&self.env);
 
         Ok(context.into_types())
     }
@@ -2,24 +2,39 @@ pub struct Env {
     word_size: WordSize,
     short_enums: bool,
     signed_char: bool,
+    endianness: Endianness,
 }
 
 pub struct EnvOptions {
     pub word_size: WordSize,
     pub short_enums: bool,
     pub signed_char: bool,
+    /// Byte order of the target, which decides the reported bit positions of bitfields in their
+    /// storage units. It is not passed to clang, so offsets are still those of the host's layout,
+    /// which match big-endian targets with the same word size and alignment rules.
+    pub endianness: Endianness,
 }
 
 impl Env {
     pub fn new(options: EnvOptions) -> Self {
-        let EnvOptions { word_size, short_enums, signed_char } = options;
-        Env { word_size, short_enums, signed_char }
+        let EnvOptions { word_size, short_enums, signed_char, endianness } = options;
+        Env { word_size, short_enums, signed_char, endianness }
     }
 
     pub fn word_size(&self) -> &WordSize {
         &self.word_size
     }
 
+    /// Byte order used to report bitfield positions, see [`EnvOptions::endianness`]
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Whether plain `char` is signed
+    pub fn signed_char(&self) -> bool {
+        self.signed_char
+    }
+
     pub fn short_enums_clang_arg(&self) -> &'static str {
         if self.short_enums { "-fshort-enums" } else { "-fno-short-enums" }
     }
@@ -31,10 +46,21 @@ impl Env {
 
 impl Default for EnvOptions {
     fn default() -> Self {
-        EnvOptions { word_size: WordSize::Size64, short_enums: true, signed_char: true }
+        EnvOptions {
+            word_size: WordSize::Size64,
+            short_enums: true,
+            signed_char: true,
+            endianness: Endianness::Little,
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
 pub enum WordSize {
     Size16,
     Size32,
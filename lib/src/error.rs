@@ -40,6 +40,8 @@ pub enum ParseError {
     Sizeof { type_name: String, error: clang::SizeofError },
     #[snafu(display("Failed to get alignment of type {type_name}: {error}"))]
     Alignof { type_name: String, error: clang::AlignofError },
+    #[snafu(display("Conflicting offset comments at {location}"))]
+    ConflictingComments { location: String },
     #[snafu(display("Invalid fields in {struct_name}: {field_names:?}"))]
     InvalidFields { field_names: Vec<String>, struct_name: String },
     #[snafu(transparent)]
@@ -1,9 +1,11 @@
 mod crawler;
 mod env;
 pub mod error;
+mod offset_comments;
 mod parser;
 mod types;
 
 pub use crawler::*;
 pub use env::*;
+pub use offset_comments::{CommentStyle, OffsetCommentMismatch, OffsetCommentOptions};
 pub use types::*;
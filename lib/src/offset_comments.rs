@@ -0,0 +1,463 @@
+use std::{fmt::Display, ops::Range};
+
+use crate::{
+    Env, SourceLocation, TypeKind, Types,
+    error::{ConflictingCommentsSnafu, ParseError},
+    parse_integer,
+};
+
+/// Options for checking and rewriting the offset comments of the records in a file
+#[derive(Debug, Clone)]
+pub struct OffsetCommentOptions {
+    /// Also check or write `/* size 0x40 */` comments after the closing brace of each record
+    pub sizes: bool,
+    /// Minimum number of hex digits in written comments, padded with zeros
+    pub hex_width: usize,
+    pub uppercase_hex: bool,
+    /// Style of inserted offset comments. Fields which do not start their line get a block
+    /// comment either way. Existing comments keep their style.
+    pub offset_comment_style: CommentStyle,
+    /// Style of inserted size comments. Existing comments keep their style.
+    pub size_comment_style: CommentStyle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// `/* 0x40 */` before the field, or `/* size 0x40 */` after the record
+    Block,
+    /// `// 0x40` on the line above the field, or `// size = 0x40` after the record
+    Line,
+}
+
+impl Default for OffsetCommentOptions {
+    fn default() -> Self {
+        OffsetCommentOptions {
+            sizes: false,
+            hex_width: 2,
+            uppercase_hex: true,
+            offset_comment_style: CommentStyle::Block,
+            size_comment_style: CommentStyle::Block,
+        }
+    }
+}
+
+impl OffsetCommentOptions {
+    fn format_hex(&self, value: usize) -> String {
+        let width = self.hex_width;
+        if self.uppercase_hex {
+            format!("0x{value:0width$X}")
+        } else {
+            format!("0x{value:0width$x}")
+        }
+    }
+
+    fn format_offset(&self, offset: usize, style: CommentStyle) -> String {
+        match style {
+            CommentStyle::Block => format!("/* {} */", self.format_hex(offset)),
+            CommentStyle::Line => format!("// {}", self.format_hex(offset)),
+        }
+    }
+
+    fn format_size(&self, size: usize, style: CommentStyle) -> String {
+        match style {
+            CommentStyle::Block => format!("/* size {} */", self.format_hex(size)),
+            CommentStyle::Line => format!("// size = {}", self.format_hex(size)),
+        }
+    }
+}
+
+/// Offset or size comment in a header which disagrees with the layout computed by clang
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffsetCommentMismatch {
+    record: String,
+    /// `None` for size comments
+    field: Option<String>,
+    location: SourceLocation,
+    expected: usize,
+    found: usize,
+}
+
+impl OffsetCommentMismatch {
+    pub fn record(&self) -> &str {
+        &self.record
+    }
+
+    /// Name of the field whose offset comment is wrong, or `None` if the record's size comment is
+    /// wrong. Unnamed fields such as anonymous unions are named `<anon>`.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// Start of the field's declaration, or the closing brace of the record for size comments
+    pub fn location(&self) -> &SourceLocation {
+        &self.location
+    }
+
+    /// Offset or size in bytes computed by clang
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+
+    /// Offset or size in bytes written in the comment
+    pub fn found(&self) -> usize {
+        self.found
+    }
+}
+
+impl Display for OffsetCommentMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.field {
+            Some(field) => write!(
+                f,
+                "{}: {}::{} is at offset {:#x}, but its comment says {:#x}",
+                self.location, self.record, field, self.expected, self.found
+            ),
+            None => write!(
+                f,
+                "{}: {} has size {:#x}, but its comment says {:#x}",
+                self.location, self.record, self.expected, self.found
+            ),
+        }
+    }
+}
+
+/// Field offset or record size, and the comment stating it in the source if there is one
+pub(crate) struct Annotation {
+    record: String,
+    field: Option<String>,
+    location: SourceLocation,
+    expected: usize,
+    comment: Option<Comment>,
+    /// Byte offset in the source where a missing comment goes
+    insert_at: usize,
+}
+
+/// Comment containing a number, as in `/* 0x24 */`
+struct Comment {
+    /// Byte range of the whole comment in the source
+    range: Range<usize>,
+    value: usize,
+    style: CommentStyle,
+}
+
+impl Annotation {
+    /// Collects the offset and size annotations of every record defined in the main file. Layouts
+    /// are built from the record declarations alone, so other declarations in the file which the
+    /// crawler does not support cannot prevent checking the records. Records which fail to build
+    /// are left out.
+    pub(crate) fn collect(env: &Env, root: &clang::Entity, source: &str) -> Vec<Self> {
+        let types = Types::new();
+        let mut annotations = Vec::new();
+        Self::collect_scope(env, &types, root, source, &mut annotations);
+        annotations
+    }
+
+    fn collect_scope(
+        env: &Env,
+        types: &Types,
+        node: &clang::Entity,
+        source: &str,
+        out: &mut Vec<Self>,
+    ) {
+        let children = node.get_children();
+        for child in &children {
+            if !child.get_location().is_some_and(|l| l.is_in_main_file()) {
+                continue;
+            }
+            match child.get_kind() {
+                clang::EntityKind::Namespace | clang::EntityKind::LinkageSpec => {
+                    Self::collect_scope(env, types, child, source, out);
+                }
+                clang::EntityKind::StructDecl
+                | clang::EntityKind::ClassDecl
+                | clang::EntityKind::UnionDecl => {
+                    // Anonymous members are annotated along with the record containing them
+                    if !child.is_definition()
+                        || child.get_template().is_some()
+                        || child.is_anonymous()
+                    {
+                        continue;
+                    }
+                    let Some(name) = record_name(child, &children) else {
+                        continue;
+                    };
+                    // Build the layout from the declaration itself, since records in different
+                    // scopes may share a name
+                    let Some(ty) = child.get_type().and_then(|t| TypeKind::new(env, types, t).ok())
+                    else {
+                        continue;
+                    };
+                    Self::collect_fields(types, &name, &ty, child, 0, source, out);
+                    Self::collect_size(types, &name, &ty, child, source, out);
+                    // Records defined inside this one
+                    Self::collect_scope(env, types, child, source, out);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_fields(
+        types: &Types,
+        record_name: &str,
+        ty: &TypeKind,
+        node: &clang::Entity,
+        base_offset: usize,
+        source: &str,
+        out: &mut Vec<Self>,
+    ) {
+        let Some(record) = ty.as_record(types) else {
+            return;
+        };
+        let field_nodes = node.get_type().and_then(|t| t.get_fields()).unwrap_or_default();
+        let mut previous_start = None;
+        for (field, field_node) in record.fields().iter().zip(&field_nodes) {
+            let offset = base_offset + field.offset_bytes();
+            let Some(start) = field_node.get_range().map(|r| r.get_start()) else {
+                continue;
+            };
+            let start_offset = start.get_file_location().offset as usize;
+            // Fields declared together, as in `int x, y;`, share a single comment
+            if is_written_in_main_file(start) && previous_start != Some(start_offset) {
+                if let Some(location) = SourceLocation::at(start) {
+                    out.push(Self {
+                        record: record_name.to_string(),
+                        field: Some(field.name().unwrap_or("<anon>").to_string()),
+                        location,
+                        expected: offset,
+                        comment: Comment::before(source, start_offset),
+                        insert_at: start_offset,
+                    });
+                }
+                previous_start = Some(start_offset);
+            }
+
+            if field.name().is_none()
+                && let Some(declaration) = field_node.get_type().and_then(|t| t.get_declaration())
+            {
+                // Members of anonymous structs and unions are commented with their offsets in
+                // the outer record
+                Self::collect_fields(
+                    types,
+                    record_name,
+                    field.kind(),
+                    &declaration,
+                    offset,
+                    source,
+                    out,
+                );
+            }
+        }
+    }
+
+    fn collect_size(
+        types: &Types,
+        record_name: &str,
+        ty: &TypeKind,
+        node: &clang::Entity,
+        source: &str,
+        out: &mut Vec<Self>,
+    ) {
+        let Some(end) = node.get_range().map(|r| r.get_end()) else {
+            return;
+        };
+        if !is_written_in_main_file(end) {
+            return;
+        }
+        let Some(location) = SourceLocation::at(end) else {
+            return;
+        };
+        let end_offset = end.get_file_location().offset as usize;
+        let Some(rest) = source.get(end_offset..) else {
+            return;
+        };
+        let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+        // A missing size comment goes at the end of the line, or before a trailing line comment
+        let insert_at = end_offset + line.find("//").unwrap_or(line.trim_end().len());
+        out.push(Self {
+            record: record_name.to_string(),
+            field: None,
+            location,
+            expected: ty.size(types),
+            comment: Comment::size_after(source, end_offset),
+            insert_at,
+        });
+    }
+
+    pub(crate) fn is_size(&self) -> bool {
+        self.field.is_none()
+    }
+
+    /// Returns the mismatch if the annotation has a comment which disagrees with the layout
+    pub(crate) fn mismatch(&self) -> Option<OffsetCommentMismatch> {
+        let comment = self.comment.as_ref()?;
+        if comment.value == self.expected {
+            return None;
+        }
+        Some(OffsetCommentMismatch {
+            record: self.record.clone(),
+            field: self.field.clone(),
+            location: self.location.clone(),
+            expected: self.expected,
+            found: comment.value,
+        })
+    }
+
+    fn format(&self, options: &OffsetCommentOptions, style: CommentStyle) -> String {
+        if self.is_size() {
+            options.format_size(self.expected, style)
+        } else {
+            options.format_offset(self.expected, style)
+        }
+    }
+
+    /// Returns the source range to replace and its replacement to make the comment agree with the
+    /// layout and options, or `None` if it already does
+    fn edit(&self, source: &str, options: &OffsetCommentOptions) -> Option<(Range<usize>, String)> {
+        match &self.comment {
+            Some(comment) => {
+                let text = self.format(options, comment.style);
+                (source[comment.range.clone()] != text).then(|| (comment.range.clone(), text))
+            }
+            None if self.is_size() => {
+                let text = if source[self.insert_at..].starts_with("//") {
+                    // A line comment would swallow the existing one
+                    format!("{} ", self.format(options, CommentStyle::Block))
+                } else {
+                    format!(" {}", self.format(options, options.size_comment_style))
+                };
+                Some((self.insert_at..self.insert_at, text))
+            }
+            None => {
+                let line_start = source[..self.insert_at].rfind('\n').map_or(0, |i| i + 1);
+                let indent = &source[line_start..self.insert_at];
+                if options.offset_comment_style == CommentStyle::Line && indent.trim().is_empty() {
+                    // On its own line above the field, with the same indentation
+                    let newline =
+                        if source[..line_start].ends_with("\r\n") { "\r\n" } else { "\n" };
+                    let text = self.format(options, CommentStyle::Line);
+                    Some((line_start..line_start, format!("{indent}{text}{newline}")))
+                } else {
+                    let text = self.format(options, CommentStyle::Block);
+                    Some((self.insert_at..self.insert_at, format!("{text} ")))
+                }
+            }
+        }
+    }
+
+    /// Inserts missing comments and corrects wrong ones, leaving the rest of the source as is.
+    /// Fails if two annotations would change the same part of the source differently.
+    pub(crate) fn rewrite(
+        annotations: &[Self],
+        source: &str,
+        options: &OffsetCommentOptions,
+    ) -> Result<String, ParseError> {
+        let mut edits = annotations
+            .iter()
+            .filter(|a| options.sizes || !a.is_size())
+            .filter_map(|a| Some((a.edit(source, options)?, &a.location)))
+            .collect::<Vec<_>>();
+        // Stable, so comments inserted at the same position stay in the order of the annotations
+        edits.sort_by_key(|((range, _), _)| (range.start, range.end));
+        let mut merged: Vec<(Range<usize>, String)> = Vec::with_capacity(edits.len());
+        for ((range, text), location) in edits {
+            match merged.last_mut() {
+                Some((last, last_text)) if range.is_empty() && *last == range => {
+                    last_text.push_str(&text);
+                }
+                Some((last, last_text)) if *last == range && *last_text == text => {}
+                Some((last, _)) if range.start < last.end => {
+                    return ConflictingCommentsSnafu { location: location.to_string() }.fail();
+                }
+                _ => merged.push((range, text)),
+            }
+        }
+
+        let mut result = String::with_capacity(source.len());
+        let mut position = 0;
+        for (range, text) in merged {
+            result.push_str(&source[position..range.start]);
+            result.push_str(&text);
+            position = range.end;
+        }
+        result.push_str(&source[position..]);
+        Ok(result)
+    }
+}
+
+/// Name of a record, or of the typedef naming it as in `typedef struct { ... } Foo;`
+fn record_name(node: &clang::Entity, siblings: &[clang::Entity]) -> Option<String> {
+    if let Some(name) = node.get_name().filter(|name| !name.is_empty()) {
+        return Some(name);
+    }
+    siblings
+        .iter()
+        .filter(|s| {
+            matches!(
+                s.get_kind(),
+                clang::EntityKind::TypedefDecl | clang::EntityKind::TypeAliasDecl
+            )
+        })
+        .find(|s| {
+            s.get_typedef_underlying_type()
+                .and_then(|t| t.get_canonical_type().get_declaration())
+                .is_some_and(|d| d.get_canonical_entity() == node.get_canonical_entity())
+        })
+        .and_then(|s| s.get_name())
+}
+
+/// Whether a location is written in the main file rather than produced by a macro, so that its
+/// offset points at the declaration's own text
+fn is_written_in_main_file(location: clang::source::SourceLocation) -> bool {
+    location.is_in_main_file()
+        && location.get_file_location().offset == location.get_expansion_location().offset
+}
+
+impl Comment {
+    /// Finds a comment containing only a number just before `offset`, either a block comment on
+    /// the same line or a line comment alone on the line above
+    fn before(source: &str, offset: usize) -> Option<Self> {
+        let before = source.get(..offset)?.trim_end_matches([' ', '\t']);
+        if let Some(inner) = before.strip_suffix("*/") {
+            let start = inner.rfind("/*")?;
+            if inner[start..].contains('\n') {
+                return None;
+            }
+            let value = parse_integer(inner[start + 2..].trim())?;
+            return Some(Self {
+                range: start..before.len(),
+                value: value as usize,
+                style: CommentStyle::Block,
+            });
+        }
+
+        // `offset` must start its line for the comment above to belong to it
+        let above = before.strip_suffix('\n')?.trim_end_matches('\r').trim_end();
+        let line_start = above.rfind('\n').map_or(0, |i| i + 1);
+        let line = &above[line_start..];
+        let start = line_start + (line.len() - line.trim_start().len());
+        let text = above[start..].strip_prefix("//")?;
+        let value = parse_integer(text.trim())?;
+        Some(Self { range: start..above.len(), value: value as usize, style: CommentStyle::Line })
+    }
+
+    /// Finds a comment such as `/* size 0x40 */` or `// size = 0x40` between the closing brace at
+    /// `offset` and the end of its line. A comment after another closing brace belongs to the
+    /// enclosing record instead.
+    fn size_after(source: &str, offset: usize) -> Option<Self> {
+        let rest = source.get(offset..)?;
+        let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+        let line = &line[..line.get(1..).and_then(|l| l.find('}')).map_or(line.len(), |i| i + 1)];
+        let start = line.find("/*").or_else(|| line.find("//"))?;
+        let (text, end, style) = if line[start..].starts_with("/*") {
+            let end = start + line[start..].find("*/")? + 2;
+            (&line[start + 2..end - 2], end, CommentStyle::Block)
+        } else {
+            (&line[start + 2..], line.trim_end().len(), CommentStyle::Line)
+        };
+        let text = text.trim().strip_prefix("size")?.trim_start();
+        let text = text.strip_prefix(['=', ':']).unwrap_or(text).trim();
+        let value = parse_integer(text)?;
+        Some(Self { range: offset + start..offset + end, value: value as usize, style })
+    }
+}
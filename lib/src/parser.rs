@@ -1,15 +1,40 @@
 use crate::{
-    EnumDecl, Env, StructDecl, TypeKind, Typedef, Types, UnionDecl,
+    EnumDecl, Env, Function, GlobalConstant, GlobalVariable, MacroDefinition, StructDecl, TypeKind,
+    Typedef, Types, UnionDecl,
     error::{InvalidAstSnafu, ParseError, UnsupportedEntitySnafu},
 };
 
 pub struct Parser {
     types: Types,
+    macro_definitions: Vec<MacroDefinition>,
 }
 
 impl Parser {
     pub fn new() -> Self {
-        Parser { types: Types::new() }
+        Parser { types: Types::new(), macro_definitions: Vec::new() }
+    }
+
+    /// Adds the macros of the main file whose bodies evaluate to constants. Macros of included
+    /// headers are only used to expand them. A macro which is defined several times keeps its
+    /// last definition.
+    pub fn evaluate_macros(&mut self, env: &Env) {
+        let definitions = std::mem::take(&mut self.macro_definitions);
+        let values = (0..definitions.len())
+            .map(|index| {
+                let definition = &definitions[index];
+                let is_redefined =
+                    definitions[index + 1..].iter().any(|d| d.name() == definition.name());
+                if !definition.is_in_main_file() || definition.is_empty() || is_redefined {
+                    return None;
+                }
+                MacroDefinition::evaluate(env, &self.types, &definitions, index)
+            })
+            .collect::<Vec<_>>();
+        for (definition, value) in definitions.into_iter().zip(values) {
+            if let Some(value) = value {
+                self.types.add_macro_constant(definition.into_constant(value));
+            }
+        }
     }
 
     pub fn into_types(self) -> Types {
@@ -26,41 +51,52 @@ impl Parser {
     pub(crate) fn parse(&mut self, env: &Env, node: &clang::Entity) -> Result<(), ParseError> {
         let kind = node.get_kind();
         if kind != clang::EntityKind::NotImplemented
+            && kind != clang::EntityKind::MacroDefinition
             && !node.get_location().unwrap().is_in_main_file()
         {
-            // Skip entities not in the main file
+            // Skip entities not in the main file. Macros of all files are kept, since those of
+            // the main file may expand them.
             return Ok(());
         }
 
-        match node.get_kind() {
+        match kind {
             clang::EntityKind::NotImplemented => self.parse_children(env, node)?,
             // typedef <underlying_type> <name>;
-            clang::EntityKind::TypedefDecl => {
+            // using <name> = <underlying_type>;
+            clang::EntityKind::TypedefDecl | clang::EntityKind::TypeAliasDecl => {
                 if let Some(child) = node.get_child(0)
                     && child.get_kind() == clang::EntityKind::StructDecl
                 {
                     // Skip "typedef struct" declarations
                     return Ok(());
                 }
-                let underlying_type = node.get_typedef_underlying_type().ok_or_else(|| {
-                    InvalidAstSnafu {
-                        message: format!("TypedefDecl without underlying type: {node:?}"),
-                    }
-                    .build()
-                })?;
-                let name = node.get_name().ok_or_else(|| {
-                    InvalidAstSnafu { message: format!("TypedefDecl without name: {node:?}") }
-                        .build()
-                })?;
-                let typedef = Typedef::new(env, &self.types, name, underlying_type)?;
+                let typedef = Typedef::from_node(env, &self.types, node)?;
                 self.types.add_type(TypeKind::Typedef(Box::new(typedef)))?;
             }
             clang::EntityKind::EnumDecl => {
                 let name = node.get_name();
-                let enum_decl = EnumDecl::new(name, node)?;
-                self.types.add_type(TypeKind::Enum(enum_decl))?;
+                let enum_decl = EnumDecl::new(env, &self.types, name, node)?;
+                if enum_decl.name().is_some() {
+                    self.types.add_type(TypeKind::Enum(enum_decl))?;
+                } else {
+                    // Constants of anonymous enums are used like global constants
+                    for child in node.get_children() {
+                        let Some(constant) = child.get_name().and_then(|name| enum_decl.get(&name))
+                        else {
+                            continue;
+                        };
+                        let constant =
+                            GlobalConstant::from_enum_constant(&enum_decl, constant, &child)?;
+                        self.types.add_constant(constant);
+                    }
+                    self.types.add_anonymous_enum(enum_decl);
+                }
             }
             clang::EntityKind::StructDecl => {
+                if node.get_template().is_some() {
+                    // Skip template specializations and explicit instantiations
+                    return Ok(());
+                }
                 let name = node.get_name().ok_or_else(|| {
                     InvalidAstSnafu { message: format!("StructDecl without name: {node:?}") }
                         .build()
@@ -73,6 +109,10 @@ impl Parser {
                 self.types.add_type(TypeKind::Struct(struct_decl))?;
             }
             clang::EntityKind::ClassDecl => {
+                if node.get_template().is_some() {
+                    // Skip template specializations and explicit instantiations
+                    return Ok(());
+                }
                 let name = node.get_name().ok_or_else(|| {
                     InvalidAstSnafu { message: format!("ClassDecl without name: {node:?}") }.build()
                 })?;
@@ -88,10 +128,15 @@ impl Parser {
             clang::EntityKind::LinkageSpec => {
                 self.parse_children(env, node)?;
             }
-            clang::EntityKind::ClassTemplate => {
+            clang::EntityKind::ClassTemplate
+            | clang::EntityKind::ClassTemplatePartialSpecialization => {
                 // TODO: Handle template classes
             }
             clang::EntityKind::UnionDecl => {
+                if node.get_template().is_some() {
+                    // Skip template specializations and explicit instantiations
+                    return Ok(());
+                }
                 let name = node.get_name().ok_or_else(|| {
                     InvalidAstSnafu { message: format!("UnionDecl without name: {node:?}") }.build()
                 })?;
@@ -102,14 +147,50 @@ impl Parser {
                 self.types.add_type(TypeKind::Union(union_decl))?;
             }
 
-            clang::EntityKind::FunctionDecl => {}
-            clang::EntityKind::VarDecl => {}
+            clang::EntityKind::FunctionDecl => {
+                if let Some(function) = skip_unsupported(Function::new(env, &self.types, node))? {
+                    self.types.add_function(function);
+                }
+            }
+            clang::EntityKind::VarDecl => {
+                if let Some(constant) =
+                    skip_unsupported(GlobalConstant::new(env, &self.types, node))?.flatten()
+                {
+                    self.types.add_constant(constant);
+                }
+                if let Some(variable) =
+                    skip_unsupported(GlobalVariable::new(env, &self.types, node))?
+                {
+                    self.types.add_variable(variable);
+                }
+            }
+            // Out-of-line member function definitions
+            clang::EntityKind::Method
+            | clang::EntityKind::Constructor
+            | clang::EntityKind::Destructor
+            | clang::EntityKind::ConversionFunction => {}
+            clang::EntityKind::FunctionTemplate => {}
+            clang::EntityKind::TypeAliasTemplateDecl => {}
+            // Variable templates, `extern template` of functions, `asm(...)`, concepts, etc.
             clang::EntityKind::UnexposedDecl => {}
             clang::EntityKind::UsingDeclaration => {}
+            clang::EntityKind::UsingDirective => {}
+            clang::EntityKind::NamespaceAlias => {}
+            clang::EntityKind::StaticAssert => {}
+            clang::EntityKind::FriendDecl => {}
+            clang::EntityKind::MacroDefinition => {
+                if let Some(definition) = MacroDefinition::new(node) {
+                    self.macro_definitions.push(definition);
+                }
+            }
+            clang::EntityKind::PreprocessingDirective
+            | clang::EntityKind::MacroExpansion
+            | clang::EntityKind::InclusionDirective => {}
+            clang::EntityKind::ModuleImportDecl => {}
             _ => {
                 return UnsupportedEntitySnafu {
                     at: "global scope".to_string(),
-                    message: format!("Unsupported entity kind: {:?}", node.get_kind()),
+                    message: format!("Unsupported entity kind: {kind:?}"),
                 }
                 .fail();
             }
@@ -117,3 +198,13 @@ impl Parser {
         Ok(())
     }
 }
+
+/// Leaves out a global declaration whose type cannot be represented, such as `__int128` or vector
+/// types, instead of failing the whole parse
+fn skip_unsupported<T>(result: Result<T, ParseError>) -> Result<Option<T>, ParseError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ParseError::UnsupportedType { .. } | ParseError::UnsupportedEntity { .. }) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
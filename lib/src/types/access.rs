@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Public,
+    Protected,
+    Private,
+}
+
+impl Access {
+    pub(crate) fn of(node: &clang::Entity) -> Self {
+        node.get_accessibility().map(Self::from).unwrap_or(Access::Public)
+    }
+}
+
+impl From<clang::Accessibility> for Access {
+    fn from(value: clang::Accessibility) -> Self {
+        match value {
+            clang::Accessibility::Public => Access::Public,
+            clang::Accessibility::Protected => Access::Protected,
+            clang::Accessibility::Private => Access::Private,
+        }
+    }
+}
+
+impl Display for Access {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Access::Public => write!(f, "public"),
+            Access::Protected => write!(f, "protected"),
+            Access::Private => write!(f, "private"),
+        }
+    }
+}
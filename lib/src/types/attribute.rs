@@ -0,0 +1,176 @@
+use std::fmt::Display;
+
+/// Attribute such as `__attribute__((...))`, `[[...]]` or `alignas(...)` applied to a declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    kind: AttributeKind,
+    arguments: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeKind {
+    Annotate,
+    Deprecated,
+    Section,
+    Aligned,
+    Packed,
+    Final,
+    Override,
+    Visibility,
+    FlagEnum,
+    /// Any other attribute, by its name without surrounding underscores or namespace
+    Other(String),
+}
+
+impl Attribute {
+    /// Parses the attributes applied to a declaration
+    pub(crate) fn parse_all(node: &clang::Entity) -> Vec<Self> {
+        node.get_children()
+            .iter()
+            .filter(|c| c.is_attribute() && !Self::is_pragma_pack(c))
+            .filter_map(Self::new)
+            .collect()
+    }
+
+    /// Whether the attribute is the implicit one clang attaches to records declared under
+    /// `#pragma pack`. It is located at the `pack` token of the directive which set the value.
+    pub(crate) fn is_pragma_pack(node: &clang::Entity) -> bool {
+        node.get_kind() == clang::EntityKind::UnexposedAttr
+            && node.get_range().is_some_and(|range| {
+                matches!(range.tokenize().as_slice(), [token] if token.get_spelling() == "pack")
+            })
+    }
+
+    fn new(node: &clang::Entity) -> Option<Self> {
+        let tokens = node
+            .get_range()
+            .map(|range| range.tokenize())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| (t.get_kind(), t.get_spelling()))
+            .collect::<Vec<_>>();
+
+        let kind = match node.get_kind() {
+            clang::EntityKind::AnnotateAttr => AttributeKind::Annotate,
+            clang::EntityKind::AlignedAttr => AttributeKind::Aligned,
+            clang::EntityKind::PackedAttr => AttributeKind::Packed,
+            clang::EntityKind::FinalAttr => AttributeKind::Final,
+            clang::EntityKind::OverrideAttr => AttributeKind::Override,
+            clang::EntityKind::VisibilityAttr => AttributeKind::Visibility,
+            clang::EntityKind::FlagEnum => AttributeKind::FlagEnum,
+            _ => {
+                // Implicit attributes have no tokens to name them
+                let name = tokens
+                    .iter()
+                    .take_while(|(_, spelling)| spelling != "(")
+                    .filter(|(kind, _)| {
+                        matches!(
+                            kind,
+                            clang::token::TokenKind::Identifier | clang::token::TokenKind::Keyword
+                        )
+                    })
+                    .last()?
+                    .1
+                    .clone();
+                AttributeKind::from_name(&name)
+            }
+        };
+
+        let arguments = if kind == AttributeKind::Annotate {
+            // The annotation may come from a macro, so its tokens can't be relied on
+            node.get_display_name().into_iter().collect()
+        } else {
+            Self::parse_arguments(&tokens)
+        };
+
+        Some(Self { kind, arguments })
+    }
+
+    fn parse_arguments(tokens: &[(clang::token::TokenKind, String)]) -> Vec<String> {
+        let Some(start) = tokens.iter().position(|(_, spelling)| spelling == "(") else {
+            return Vec::new();
+        };
+
+        let mut arguments = Vec::new();
+        let mut current = Vec::new();
+        let mut depth = 0;
+        for (kind, spelling) in &tokens[start + 1..] {
+            match spelling.as_str() {
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" | "}" if depth == 0 => break,
+                ")" | "]" | "}" => depth -= 1,
+                "," if depth == 0 => {
+                    arguments.push(Self::join_argument(&current));
+                    current.clear();
+                    continue;
+                }
+                _ => {}
+            }
+            current.push((*kind, spelling.as_str()));
+        }
+        if !current.is_empty() {
+            arguments.push(Self::join_argument(&current));
+        }
+        arguments
+    }
+
+    fn join_argument(tokens: &[(clang::token::TokenKind, &str)]) -> String {
+        if let [(clang::token::TokenKind::Literal, literal)] = tokens
+            && let Some(string) = literal.strip_prefix('"').and_then(|s| s.strip_suffix('"'))
+        {
+            return string.to_string();
+        }
+        tokens.iter().map(|(_, spelling)| *spelling).collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn kind(&self) -> &AttributeKind {
+        &self.kind
+    }
+
+    pub fn arguments(&self) -> &[String] {
+        &self.arguments
+    }
+}
+
+impl AttributeKind {
+    fn from_name(name: &str) -> Self {
+        let name = name.strip_prefix("__").and_then(|n| n.strip_suffix("__")).unwrap_or(name);
+        match name {
+            "annotate" => AttributeKind::Annotate,
+            "deprecated" => AttributeKind::Deprecated,
+            "section" => AttributeKind::Section,
+            "aligned" | "alignas" | "_Alignas" => AttributeKind::Aligned,
+            "packed" => AttributeKind::Packed,
+            "final" => AttributeKind::Final,
+            "override" => AttributeKind::Override,
+            "visibility" => AttributeKind::Visibility,
+            "flag_enum" => AttributeKind::FlagEnum,
+            _ => AttributeKind::Other(name.to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            AttributeKind::Annotate => "annotate",
+            AttributeKind::Deprecated => "deprecated",
+            AttributeKind::Section => "section",
+            AttributeKind::Aligned => "aligned",
+            AttributeKind::Packed => "packed",
+            AttributeKind::Final => "final",
+            AttributeKind::Override => "override",
+            AttributeKind::Visibility => "visibility",
+            AttributeKind::FlagEnum => "flag_enum",
+            AttributeKind::Other(name) => name,
+        }
+    }
+}
+
+impl Display for Attribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind.name())?;
+        if !self.arguments.is_empty() {
+            write!(f, "({})", self.arguments.join(", "))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{Endianness, Env};
+
+/// Storage unit holding a bitfield, which is the unit extraction code must load before shifting
+/// and masking out the bitfield's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitFieldUnit {
+    /// Offset in bytes of the storage unit
+    offset: usize,
+    /// Size in bytes of the storage unit, which is the size of the declared type
+    size: usize,
+    /// Position of the bitfield's least significant bit, counted from the least significant bit
+    /// of the storage unit once loaded in the target's byte order
+    bit_position: usize,
+    width: u8,
+}
+
+impl BitFieldUnit {
+    /// Locates the storage unit of a bitfield at `offset` bits, following the Itanium C++ ABI
+    /// where a bitfield of type `T` lives in a `sizeof(T)`-aligned unit of `sizeof(T)` bytes
+    pub(crate) fn new(env: &Env, offset: usize, width: u8, container_size: usize) -> Self {
+        let size = container_size.max(1);
+        let width_bits = width as usize;
+        let mut unit_offset = offset / 8 / size * size;
+        if offset + width_bits > (unit_offset + size) * 8 {
+            // Packed bitfields may straddle aligned units, so start the unit at their first byte
+            unit_offset = offset / 8;
+        }
+        let start = offset - unit_offset * 8;
+        let bit_position = match env.endianness() {
+            Endianness::Little => start,
+            // Big-endian targets allocate bitfields from the most significant bit
+            Endianness::Big => (size * 8).saturating_sub(start + width_bits),
+        };
+        Self { offset: unit_offset, size, bit_position, width }
+    }
+
+    pub(crate) fn shifted(&self, offset: usize) -> Self {
+        Self { offset: self.offset + offset, ..*self }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn bit_position(&self) -> usize {
+        self.bit_position
+    }
+
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// Mask of the bitfield's bits within the loaded storage unit
+    pub fn mask(&self) -> u128 {
+        let bits = if self.width >= 128 { u128::MAX } else { (1u128 << self.width) - 1 };
+        bits << self.bit_position
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Display;
+
+/// Calling convention of a function type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallingConvention {
+    Cdecl,
+    Stdcall,
+    Fastcall,
+    Thiscall,
+    Pascal,
+    Vectorcall,
+    RegCall,
+    Aapcs,
+    AapcsVfp,
+    SysV64,
+    Win64,
+    /// Any other convention, such as `swiftcall` or `preserve_most`
+    Other,
+}
+
+impl From<clang::CallingConvention> for CallingConvention {
+    fn from(value: clang::CallingConvention) -> Self {
+        match value {
+            clang::CallingConvention::Cdecl => CallingConvention::Cdecl,
+            clang::CallingConvention::Stdcall => CallingConvention::Stdcall,
+            clang::CallingConvention::Fastcall => CallingConvention::Fastcall,
+            clang::CallingConvention::Thiscall => CallingConvention::Thiscall,
+            clang::CallingConvention::Pascal => CallingConvention::Pascal,
+            clang::CallingConvention::Vectorcall => CallingConvention::Vectorcall,
+            clang::CallingConvention::RegCall => CallingConvention::RegCall,
+            clang::CallingConvention::Aapcs => CallingConvention::Aapcs,
+            clang::CallingConvention::AapcsVfp => CallingConvention::AapcsVfp,
+            clang::CallingConvention::SysV64 => CallingConvention::SysV64,
+            clang::CallingConvention::Win64 => CallingConvention::Win64,
+            clang::CallingConvention::Unexposed
+            | clang::CallingConvention::Swift
+            | clang::CallingConvention::PreserveMost
+            | clang::CallingConvention::PreserveAll
+            | clang::CallingConvention::IntelOcl => CallingConvention::Other,
+        }
+    }
+}
+
+impl Display for CallingConvention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CallingConvention::Cdecl => write!(f, "cdecl"),
+            CallingConvention::Stdcall => write!(f, "stdcall"),
+            CallingConvention::Fastcall => write!(f, "fastcall"),
+            CallingConvention::Thiscall => write!(f, "thiscall"),
+            CallingConvention::Pascal => write!(f, "pascal"),
+            CallingConvention::Vectorcall => write!(f, "vectorcall"),
+            CallingConvention::RegCall => write!(f, "regcall"),
+            CallingConvention::Aapcs => write!(f, "aapcs"),
+            CallingConvention::AapcsVfp => write!(f, "aapcs-vfp"),
+            CallingConvention::SysV64 => write!(f, "sysv_abi"),
+            CallingConvention::Win64 => write!(f, "ms_abi"),
+            CallingConvention::Other => write!(f, "other"),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+/// Documentation comment attached to a declaration, such as `/** ... */` or `///`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocComment {
+    raw: String,
+    brief: Option<String>,
+}
+
+impl DocComment {
+    pub(crate) fn of(node: &clang::Entity) -> Option<Self> {
+        let raw = node.get_comment()?;
+        let brief = node.get_comment_brief().filter(|brief| !brief.is_empty());
+        Some(Self { raw, brief })
+    }
+
+    /// Comment as written in the source, including the comment markers
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// First paragraph of the comment, or the `\brief` paragraph if there is one
+    pub fn brief(&self) -> Option<&str> {
+        self.brief.as_deref()
+    }
+
+    /// Comment without its markers and the `*` which commonly starts each line of a block comment
+    pub fn text(&self) -> String {
+        let lines = self
+            .raw
+            .lines()
+            .map(|line| {
+                let line = line.trim();
+                let line = ["///<", "//!<", "///", "//!", "/**<", "/*!<", "/**", "/*!", "//", "/*"]
+                    .iter()
+                    .find_map(|marker| line.strip_prefix(marker))
+                    .unwrap_or(line);
+                let line = line.strip_suffix("*/").unwrap_or(line);
+                let line = match line.strip_prefix('*') {
+                    Some(rest) if !rest.starts_with('/') => rest,
+                    _ => line,
+                };
+                line.trim()
+            })
+            .collect::<Vec<_>>();
+        let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+        let end = lines.iter().rposition(|l| !l.is_empty()).map_or(start, |i| i + 1);
+        lines[start..end].join("\n")
+    }
+}
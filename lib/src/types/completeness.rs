@@ -0,0 +1,21 @@
+/// Whether a record has been defined, as opposed to only declared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completeness {
+    /// Declared without a definition, such as `struct Tag;`
+    ForwardDeclared,
+    /// Defined without any members, such as `struct Tag {};`
+    Empty,
+    Complete,
+}
+
+impl Completeness {
+    pub(crate) fn of(node: &clang::Entity, has_members: bool) -> Self {
+        if node.get_definition().is_none() {
+            Completeness::ForwardDeclared
+        } else if has_members {
+            Completeness::Complete
+        } else {
+            Completeness::Empty
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt::Display;
+
+/// Compile-time value of a constant expression
+#[derive(Debug, Clone)]
+pub enum ConstantValue {
+    Signed(i64),
+    Unsigned(u64),
+    Float(f64),
+    String(String),
+}
+
+impl ConstantValue {
+    pub(crate) fn evaluate(node: &clang::Entity) -> Option<Self> {
+        match node.evaluate()? {
+            clang::EvaluationResult::SignedInteger(value) => Some(ConstantValue::Signed(value)),
+            clang::EvaluationResult::UnsignedInteger(value) => Some(ConstantValue::Unsigned(value)),
+            clang::EvaluationResult::Float(value) => Some(ConstantValue::Float(value)),
+            clang::EvaluationResult::String(value)
+            | clang::EvaluationResult::ObjCString(value)
+            | clang::EvaluationResult::CFString(value) => {
+                Some(ConstantValue::String(value.to_string_lossy().into_owned()))
+            }
+            clang::EvaluationResult::Unexposed | clang::EvaluationResult::Other(_) => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            ConstantValue::Signed(value) => Some(*value),
+            ConstantValue::Unsigned(value) => i64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            ConstantValue::Signed(value) => u64::try_from(*value).ok(),
+            ConstantValue::Unsigned(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ConstantValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ConstantValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for ConstantValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ConstantValue::Signed(a), ConstantValue::Signed(b)) => a == b,
+            (ConstantValue::Unsigned(a), ConstantValue::Unsigned(b)) => a == b,
+            // Compare bit patterns so that equality stays reflexive for NaN
+            (ConstantValue::Float(a), ConstantValue::Float(b)) => a.to_bits() == b.to_bits(),
+            (ConstantValue::String(a), ConstantValue::String(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ConstantValue {}
+
+impl Display for ConstantValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstantValue::Signed(value) => write!(f, "{value}"),
+            ConstantValue::Unsigned(value) => write!(f, "{value}"),
+            ConstantValue::Float(value) => write!(f, "{value}"),
+            ConstantValue::String(value) => write!(f, "{value:?}"),
+        }
+    }
+}
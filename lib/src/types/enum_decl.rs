@@ -1,22 +1,49 @@
 use std::fmt::Display;
 
-use crate::error::{InvalidAstSnafu, ParseError, SizeofSnafu};
+use crate::{
+    Attribute, AttributeKind, DocComment, Env, TypeKind, Types,
+    error::{AlignofSnafu, InvalidAstSnafu, ParseError, SizeofSnafu},
+    types::source::{join_tokens, macro_names, parse_integer},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnumDecl {
     pub(crate) name: Option<String>,
     constants: Vec<EnumConstant>,
+    /// Integer type the enum is stored as, with typedefs resolved
+    underlying_type: Box<TypeKind>,
+    is_signed: bool,
+    is_scoped: bool,
+    has_fixed_underlying_type: bool,
+    is_flag_enum: bool,
     size: usize,
+    alignment: usize,
+    attributes: Vec<Attribute>,
+    comment: Option<DocComment>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnumConstant {
     name: String,
     value: i64,
+    unsigned_value: u64,
+    /// Whether the value is interpreted as signed, following the enum's underlying type
+    is_signed: bool,
+    /// Initializer as written in the source, before macro expansion
+    expression: Option<String>,
+    /// Macros referenced by the initializer
+    macros: Vec<String>,
+    attributes: Vec<Attribute>,
+    comment: Option<DocComment>,
 }
 
 impl EnumDecl {
-    pub fn new(name: Option<String>, node: &clang::Entity) -> Result<Self, ParseError> {
+    pub fn new(
+        env: &Env,
+        types: &Types,
+        name: Option<String>,
+        node: &clang::Entity,
+    ) -> Result<Self, ParseError> {
         if node.get_kind() != clang::EntityKind::EnumDecl {
             return InvalidAstSnafu { message: format!("Expected EnumDecl, found: {node:?}") }
                 .fail();
@@ -29,9 +56,19 @@ impl EnumDecl {
         let size = underlying_type.get_sizeof().map_err(|e| {
             SizeofSnafu { type_name: underlying_type.get_display_name(), error: e }.build()
         })?;
+        let alignment = underlying_type.get_alignof().map_err(|e| {
+            AlignofSnafu { type_name: underlying_type.get_display_name(), error: e }.build()
+        })?;
+        let canonical_type = underlying_type.get_canonical_type();
+        let is_signed = canonical_type.is_signed_integer();
+        let is_scoped = node.is_scoped();
+        let has_fixed_underlying_type = is_scoped || Self::has_explicit_underlying_type(node);
 
         let mut constants = Vec::new();
         for child in node.get_children() {
+            if child.is_attribute() {
+                continue;
+            }
             if child.get_kind() != clang::EntityKind::EnumConstantDecl {
                 return InvalidAstSnafu {
                     message: format!("Expected EnumConstantDecl, found: {child:?}"),
@@ -42,14 +79,94 @@ impl EnumDecl {
                 { InvalidAstSnafu { message: format!("EnumConstantDecl without name: {child:?}") } }
                     .build()
             })?;
-            let (value, _) = child.get_enum_constant_value().ok_or_else(|| {
+            let (value, unsigned_value) = child.get_enum_constant_value().ok_or_else(|| {
                 InvalidAstSnafu { message: format!("EnumConstantDecl without value: {child:?}") }
                     .build()
             })?;
-            constants.push(EnumConstant { name, value });
+            // libclang sign-extends the value even for unsigned enums
+            let value = if is_signed { value } else { unsigned_value as i64 };
+            let (expression, macros) = Self::parse_initializer(&child);
+            let attributes = Attribute::parse_all(&child);
+            constants.push(EnumConstant {
+                name,
+                value,
+                unsigned_value,
+                is_signed,
+                expression,
+                macros,
+                attributes,
+                comment: DocComment::of(&child),
+            });
+        }
+
+        let attributes = Attribute::parse_all(node);
+        let is_flag_enum = attributes.iter().any(|a| a.kind() == &AttributeKind::FlagEnum)
+            || Self::has_flag_values(&constants);
+        Ok(EnumDecl {
+            name,
+            constants,
+            underlying_type: Box::new(TypeKind::new(env, types, canonical_type)?),
+            is_signed,
+            is_scoped,
+            has_fixed_underlying_type,
+            is_flag_enum,
+            size,
+            alignment,
+            attributes,
+            comment: DocComment::of(node),
+        })
+    }
+
+    /// Whether the constants look like bit flags: each one is zero, a single bit or a combination
+    /// of single-bit constants, and the values don't simply count up like a regular enum
+    fn has_flag_values(constants: &[EnumConstant]) -> bool {
+        if constants.iter().any(|c| c.is_signed && c.value < 0) {
+            return false;
+        }
+        let single_bits = constants
+            .iter()
+            .filter(|c| c.unsigned_value.is_power_of_two())
+            .fold(0, |bits, c| bits | c.unsigned_value);
+        if single_bits.count_ones() < 2 {
+            return false;
+        }
+        if constants.iter().any(|c| c.unsigned_value & !single_bits != 0) {
+            return false;
         }
 
-        Ok(EnumDecl { name, constants, size })
+        let mut values = constants.iter().map(|c| c.unsigned_value).collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+        let is_sequential = values.windows(2).all(|w| w[1] == w[0] + 1);
+        !is_sequential
+    }
+
+    /// Returns the source text of an enum constant's initializer and the macros it references
+    fn parse_initializer(node: &clang::Entity) -> (Option<String>, Vec<String>) {
+        let tokens = node.get_range().map(|range| range.tokenize()).unwrap_or_default();
+        let Some(equals) = tokens.iter().position(|t| t.get_spelling() == "=") else {
+            return (None, Vec::new());
+        };
+        let initializer = &tokens[equals + 1..];
+        (Some(join_tokens(initializer)), macro_names(node, initializer))
+    }
+
+    /// Whether the enum declares its underlying type, as in `enum Kind : unsigned char`
+    fn has_explicit_underlying_type(node: &clang::Entity) -> bool {
+        let Some(range) = node.get_range() else {
+            return false;
+        };
+        let mut depth = 0;
+        for token in range.tokenize() {
+            match token.get_spelling().as_str() {
+                "(" | "[" => depth += 1,
+                ")" | "]" => depth -= 1,
+                "{" | ";" if depth == 0 => break,
+                ":" if depth == 0 => return true,
+                _ => {}
+            }
+        }
+        false
     }
 
     pub fn size(&self) -> usize {
@@ -57,13 +174,86 @@ impl EnumDecl {
     }
 
     pub fn alignment(&self) -> usize {
-        self.size
+        self.alignment
+    }
+
+    pub fn underlying_type(&self) -> &TypeKind {
+        &self.underlying_type
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.is_signed
+    }
+
+    /// Whether the enum is declared as `enum class` or `enum struct`
+    pub fn is_scoped(&self) -> bool {
+        self.is_scoped
+    }
+
+    /// Whether the underlying type is fixed by the declaration rather than chosen from the values,
+    /// which is always the case for scoped enums
+    pub fn has_fixed_underlying_type(&self) -> bool {
+        self.has_fixed_underlying_type
+    }
+
+    /// Whether the enum is a set of bit flags, either by its `flag_enum` attribute or its values
+    pub fn is_flag_enum(&self) -> bool {
+        self.is_flag_enum
     }
 
     pub fn name(&self) -> Option<&str> {
         self.name.as_deref()
     }
 
+    /// Splits a value into the flag constants it is made of, largest combinations first, and the
+    /// bits which no constant covers
+    pub fn decompose_flags(&self, value: u64) -> (Vec<&EnumConstant>, u64) {
+        let mut flags = self
+            .constants
+            .iter()
+            .filter(|c| c.unsigned_value != 0 && c.unsigned_value & value == c.unsigned_value)
+            .collect::<Vec<_>>();
+        flags.sort_by_key(|c| std::cmp::Reverse(c.unsigned_value.count_ones()));
+
+        let mut remaining = value;
+        flags.retain(|c| {
+            let is_needed = c.unsigned_value & remaining != 0;
+            remaining &= !c.unsigned_value;
+            is_needed
+        });
+        flags.sort_by_key(|c| c.unsigned_value);
+        (flags, remaining)
+    }
+
+    /// Formats a value as flag constants, such as `Flag1 | Flag3 | 0x40`
+    pub fn format_flags(&self, value: u64) -> String {
+        if value == 0 {
+            return match self.get_by_unsigned_value(0) {
+                Some(constant) => constant.name.clone(),
+                None => "0".to_string(),
+            };
+        }
+        let (flags, remaining) = self.decompose_flags(value);
+        let mut parts = flags.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        if remaining != 0 {
+            parts.push(format!("{remaining:#x}"));
+        }
+        parts.join(" | ")
+    }
+
+    /// Parses flags formatted by [`Self::format_flags`], where each part is a constant name or an
+    /// integer literal
+    pub fn parse_flags(&self, text: &str) -> Option<u64> {
+        text.split('|').try_fold(0, |value, part| {
+            let part = part.trim();
+            let bits = match self.get(part) {
+                Some(constant) => constant.unsigned_value,
+                None => parse_integer(part)?,
+            };
+            Some(value | bits)
+        })
+    }
+
     pub fn get(&self, name: &str) -> Option<&EnumConstant> {
         self.constants.iter().find(|c| c.name == name)
     }
@@ -72,9 +262,41 @@ impl EnumDecl {
         self.constants.iter().find(|c| c.value == value)
     }
 
+    /// Lists all constants with a value, including aliases, in declaration order
+    pub fn get_all_by_value(&self, value: i64) -> Vec<&EnumConstant> {
+        self.constants.iter().filter(|c| c.value == value).collect()
+    }
+
+    /// Formats a value as the name of the first constant with that value. Values of flag enums
+    /// which no constant matches are formatted as flags, and other values as plain numbers.
+    pub fn format_value(&self, value: i64) -> String {
+        if let Some(constant) = self.get_by_value(value) {
+            constant.name.clone()
+        } else if self.is_flag_enum {
+            self.format_flags(value as u64)
+        } else if self.is_signed {
+            value.to_string()
+        } else {
+            (value as u64).to_string()
+        }
+    }
+
+    pub fn get_by_unsigned_value(&self, value: u64) -> Option<&EnumConstant> {
+        self.constants.iter().find(|c| c.unsigned_value == value)
+    }
+
     pub fn constants(&self) -> &[EnumConstant] {
         &self.constants
     }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
 }
 
 impl EnumConstant {
@@ -82,16 +304,62 @@ impl EnumConstant {
         &self.name
     }
 
+    /// Value as a signed integer. Values of unsigned enums above `i64::MAX` wrap around, see
+    /// [`Self::unsigned_value`].
     pub fn value(&self) -> i64 {
         self.value
     }
+
+    pub fn unsigned_value(&self) -> u64 {
+        self.unsigned_value
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.is_signed
+    }
+
+    /// Initializer as written in the source, or `None` if the value is implicit
+    pub fn expression(&self) -> Option<&str> {
+        self.expression.as_deref()
+    }
+
+    /// Names of the macros used in the initializer
+    pub fn macros(&self) -> &[String] {
+        &self.macros
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+}
+
+impl Display for EnumConstant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_signed && self.value < 0 {
+            write!(f, "{}: -{:#x}", self.name, self.value.unsigned_abs())
+        } else {
+            write!(f, "{}: {:#x}", self.name, self.unsigned_value)
+        }
+    }
 }
 
 impl Display for EnumDecl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{} (size={}) {{ ", self.name.as_deref().unwrap_or("<anon>"), self.size)?;
+        if self.is_scoped {
+            write!(f, "class ")?;
+        }
+        write!(f, "{}", self.name.as_deref().unwrap_or("<anon>"))?;
+        if self.has_fixed_underlying_type {
+            write!(f, " : {}", self.underlying_type)?;
+        }
+        writeln!(f, " (size={}) {{ ", self.size)?;
         for constant in &self.constants {
-            writeln!(f, "  {}: {:#x}", constant.name, constant.value)?;
+            writeln!(f, "  {constant}")?;
         }
         write!(f, "}}")?;
         Ok(())
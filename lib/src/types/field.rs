@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use crate::{
-    Env, TypeKind, Types,
+    Attribute, DocComment, Env, TypeKind, Types,
     error::{InvalidAstSnafu, ParseError},
 };
 
@@ -12,6 +12,8 @@ pub struct Field {
     constant: bool,
     volatile: bool,
     bit_field_width: Option<u8>,
+    attributes: Vec<Attribute>,
+    comment: Option<DocComment>,
 }
 
 impl Field {
@@ -37,7 +39,9 @@ impl Field {
         let constant = ty.is_const_qualified();
         let volatile = ty.is_volatile_qualified();
         let bit_field_width = field.get_bit_field_width().map(|w| w as u8);
-        Ok(Self { name, kind, constant, volatile, bit_field_width })
+        let attributes = Attribute::parse_all(field);
+        let comment = DocComment::of(field);
+        Ok(Self { name, kind, constant, volatile, bit_field_width, attributes, comment })
     }
 
     pub fn name(&self) -> Option<&str> {
@@ -60,10 +64,19 @@ impl Field {
         self.bit_field_width
     }
 
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+
+    /// Size in bytes of the field. For bitfields, this is the size of the declared type, which is
+    /// the size of the storage unit, see [`crate::StructField::bit_field_unit`].
     pub fn size(&self, types: &Types) -> usize {
-        self.bit_field_width()
-            .map(|w| w.div_ceil(8) as usize)
-            .unwrap_or_else(|| self.kind.size(types))
+        self.kind.size(types)
     }
 }
 
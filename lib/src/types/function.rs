@@ -0,0 +1,189 @@
+use std::fmt::Display;
+
+use crate::{
+    Attribute, CallingConvention, DocComment, Env, SourceLocation, StorageClass, TypeKind, Types,
+    error::{InvalidAstSnafu, ParseError},
+    types::scope::qualified_name,
+};
+
+/// Free function declared at namespace scope, such as `void Actor_Update(Actor *actor);`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    name: String,
+    qualified_name: String,
+    mangled_name: Option<String>,
+    return_type: TypeKind,
+    parameters: Vec<Parameter>,
+    is_variadic: bool,
+    /// Whether the parameters are declared, which is not the case for `int f()` in C
+    has_prototype: bool,
+    calling_convention: Option<CallingConvention>,
+    storage_class: StorageClass,
+    is_definition: bool,
+    attributes: Vec<Attribute>,
+    location: SourceLocation,
+    comment: Option<DocComment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    name: Option<String>,
+    kind: TypeKind,
+}
+
+impl Function {
+    pub fn new(env: &Env, types: &Types, node: &clang::Entity) -> Result<Self, ParseError> {
+        if node.get_kind() != clang::EntityKind::FunctionDecl {
+            return InvalidAstSnafu { message: format!("Expected FunctionDecl, found: {node:?}") }
+                .fail();
+        }
+
+        let name = node.get_name().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("FunctionDecl without name: {node:?}") }.build()
+        })?;
+        let ty = node.get_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("FunctionDecl without type: {node:?}") }.build()
+        })?;
+        let return_type = ty.get_result_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("FunctionDecl without return type: {node:?}") }
+                .build()
+        })?;
+        let location = SourceLocation::of(node).ok_or_else(|| {
+            InvalidAstSnafu { message: format!("FunctionDecl without location: {node:?}") }.build()
+        })?;
+
+        // Parameter types come from the function type, where arrays and functions have decayed
+        // to pointers, and names from the declaration
+        let parameter_types = ty.get_argument_types();
+        let has_prototype = parameter_types.is_some();
+        let parameter_nodes = node.get_arguments().unwrap_or_default();
+        let parameters = parameter_types
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(i, param_ty)| {
+                Ok(Parameter {
+                    name: parameter_nodes.get(i).and_then(|p| p.get_name()),
+                    kind: TypeKind::new(env, types, param_ty)?,
+                })
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        Ok(Self {
+            qualified_name: qualified_name(node).unwrap_or_else(|| name.clone()),
+            name,
+            mangled_name: node.get_mangled_name(),
+            return_type: TypeKind::new(env, types, return_type)?,
+            parameters,
+            is_variadic: ty.is_variadic(),
+            has_prototype,
+            calling_convention: ty.get_calling_convention().map(CallingConvention::from),
+            storage_class: StorageClass::of(node),
+            is_definition: node.is_definition(),
+            attributes: Attribute::parse_all(node),
+            location,
+            comment: DocComment::of(node),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Name including enclosing namespaces, such as `game::Actor_Update`
+    pub fn qualified_name(&self) -> &str {
+        &self.qualified_name
+    }
+
+    /// Symbol name of the function for the target, which is mangled for C++ functions
+    pub fn mangled_name(&self) -> Option<&str> {
+        self.mangled_name.as_deref()
+    }
+
+    pub fn return_type(&self) -> &TypeKind {
+        &self.return_type
+    }
+
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    /// Whether the function takes `...` after its parameters
+    pub fn is_variadic(&self) -> bool {
+        self.is_variadic
+    }
+
+    /// Whether the parameters are declared. Functions declared as `int f()` in C have no
+    /// prototype and accept any arguments.
+    pub fn has_prototype(&self) -> bool {
+        self.has_prototype
+    }
+
+    pub fn calling_convention(&self) -> Option<CallingConvention> {
+        self.calling_convention
+    }
+
+    pub fn storage_class(&self) -> StorageClass {
+        self.storage_class
+    }
+
+    /// Whether this declaration has a body, as opposed to a prototype
+    pub fn is_definition(&self) -> bool {
+        self.is_definition
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    pub fn location(&self) -> &SourceLocation {
+        &self.location
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+}
+
+impl Parameter {
+    /// Name of the parameter, or `None` if the declaration leaves it out
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn kind(&self) -> &TypeKind {
+        &self.kind
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} {}", self.kind, name),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.storage_class != StorageClass::None {
+            write!(f, "{} ", self.storage_class)?;
+        }
+        write!(f, "{} {}(", self.return_type, self.qualified_name)?;
+        for (i, parameter) in self.parameters.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{parameter}")?;
+        }
+        if self.is_variadic {
+            if !self.parameters.is_empty() {
+                write!(f, ", ")?;
+            }
+            write!(f, "...")?;
+        }
+        write!(f, ")")
+    }
+}
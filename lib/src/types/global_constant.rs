@@ -0,0 +1,142 @@
+use std::fmt::Display;
+
+use crate::{
+    ConstantValue, DocComment, EnumConstant, EnumDecl, Env, SourceLocation, TypeKind, Types,
+    error::{InvalidAstSnafu, ParseError},
+    types::scope::qualified_name,
+};
+
+/// Constant declared at namespace scope, such as `static const int kMaxHp = 100;`, a `constexpr`
+/// variable or a constant of an anonymous enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalConstant {
+    name: String,
+    qualified_name: String,
+    kind: TypeKind,
+    value: ConstantValue,
+    is_constexpr: bool,
+    location: SourceLocation,
+    comment: Option<DocComment>,
+}
+
+impl GlobalConstant {
+    /// Creates a constant from a variable declaration, or returns `None` if the variable is not
+    /// constant or its value can't be evaluated
+    pub fn new(env: &Env, types: &Types, node: &clang::Entity) -> Result<Option<Self>, ParseError> {
+        if node.get_kind() != clang::EntityKind::VarDecl {
+            return InvalidAstSnafu { message: format!("Expected VarDecl, found: {node:?}") }
+                .fail();
+        }
+
+        let ty = node.get_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("VarDecl without type: {node:?}") }.build()
+        })?;
+        let is_constexpr = Self::has_constexpr_specifier(node);
+        if !is_constexpr && !ty.is_const_qualified() {
+            return Ok(None);
+        }
+        let Some(value) = ConstantValue::evaluate(node) else {
+            return Ok(None);
+        };
+
+        let (name, qualified_name, location) = Self::describe(node)?;
+        Ok(Some(Self {
+            name,
+            qualified_name,
+            kind: TypeKind::new(env, types, ty)?,
+            value,
+            is_constexpr,
+            location,
+            comment: DocComment::of(node),
+        }))
+    }
+
+    /// Creates a constant from a constant of an anonymous enum
+    pub(crate) fn from_enum_constant(
+        enum_decl: &EnumDecl,
+        constant: &EnumConstant,
+        node: &clang::Entity,
+    ) -> Result<Self, ParseError> {
+        let (name, qualified_name, location) = Self::describe(node)?;
+        let value = if constant.is_signed() {
+            ConstantValue::Signed(constant.value())
+        } else {
+            ConstantValue::Unsigned(constant.unsigned_value())
+        };
+        Ok(Self {
+            name,
+            qualified_name,
+            kind: enum_decl.underlying_type().clone(),
+            value,
+            is_constexpr: false,
+            location,
+            comment: DocComment::of(node),
+        })
+    }
+
+    fn describe(node: &clang::Entity) -> Result<(String, String, SourceLocation), ParseError> {
+        let name = node.get_name().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("Constant without name: {node:?}") }.build()
+        })?;
+        let qualified_name = qualified_name(node).unwrap_or_else(|| name.clone());
+        let location = SourceLocation::of(node).ok_or_else(|| {
+            InvalidAstSnafu { message: format!("Constant without location: {node:?}") }.build()
+        })?;
+        Ok((name, qualified_name, location))
+    }
+
+    fn has_constexpr_specifier(node: &clang::Entity) -> bool {
+        let Some(range) = node.get_range() else {
+            return false;
+        };
+        range
+            .tokenize()
+            .iter()
+            .map(|t| t.get_spelling())
+            .take_while(|spelling| spelling != "=" && spelling != "{")
+            .any(|spelling| spelling == "constexpr")
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Name including enclosing namespaces, such as `game::kMaxHp`
+    pub fn qualified_name(&self) -> &str {
+        &self.qualified_name
+    }
+
+    pub fn kind(&self) -> &TypeKind {
+        &self.kind
+    }
+
+    pub fn value(&self) -> &ConstantValue {
+        &self.value
+    }
+
+    pub fn is_constexpr(&self) -> bool {
+        self.is_constexpr
+    }
+
+    pub fn location(&self) -> &SourceLocation {
+        &self.location
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+}
+
+impl Display for GlobalConstant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{} {} = {}",
+            if self.is_constexpr { "constexpr " } else { "const " },
+            self.kind,
+            self.qualified_name,
+            self.value
+        )
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use crate::{
+    Attribute, AttributeKind, DocComment, Env, SourceLocation, StorageClass, TypeKind, Types,
+    error::{InvalidAstSnafu, ParseError},
+    types::scope::qualified_name,
+};
+
+/// Variable declared at namespace scope, such as `extern Actor *gPlayer;`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalVariable {
+    name: String,
+    qualified_name: String,
+    kind: TypeKind,
+    storage_class: StorageClass,
+    is_definition: bool,
+    attributes: Vec<Attribute>,
+    location: SourceLocation,
+    comment: Option<DocComment>,
+}
+
+impl GlobalVariable {
+    pub fn new(env: &Env, types: &Types, node: &clang::Entity) -> Result<Self, ParseError> {
+        if node.get_kind() != clang::EntityKind::VarDecl {
+            return InvalidAstSnafu { message: format!("Expected VarDecl, found: {node:?}") }
+                .fail();
+        }
+
+        let name = node.get_name().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("VarDecl without name: {node:?}") }.build()
+        })?;
+        let ty = node.get_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("VarDecl without type: {node:?}") }.build()
+        })?;
+        let location = SourceLocation::of(node).ok_or_else(|| {
+            InvalidAstSnafu { message: format!("VarDecl without location: {node:?}") }.build()
+        })?;
+
+        Ok(Self {
+            qualified_name: qualified_name(node).unwrap_or_else(|| name.clone()),
+            name,
+            kind: TypeKind::new(env, types, ty)?,
+            storage_class: StorageClass::of(node),
+            is_definition: node.is_definition(),
+            attributes: Attribute::parse_all(node),
+            location,
+            comment: DocComment::of(node),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Name including enclosing namespaces, such as `game::gPlayer`
+    pub fn qualified_name(&self) -> &str {
+        &self.qualified_name
+    }
+
+    pub fn kind(&self) -> &TypeKind {
+        &self.kind
+    }
+
+    pub fn storage_class(&self) -> StorageClass {
+        self.storage_class
+    }
+
+    /// Whether this declaration defines the variable, as opposed to `extern` declarations
+    pub fn is_definition(&self) -> bool {
+        self.is_definition
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Name of the section given by `__attribute__((section(...)))`
+    pub fn section(&self) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.kind() == &AttributeKind::Section)
+            .and_then(|a| a.arguments().first())
+            .map(|s| s.as_str())
+    }
+
+    pub fn location(&self) -> &SourceLocation {
+        &self.location
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+}
+
+impl Display for GlobalVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.storage_class != StorageClass::None {
+            write!(f, "{} ", self.storage_class)?;
+        }
+        write!(f, "{} {}", self.kind, self.qualified_name)
+    }
+}
@@ -0,0 +1,528 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{
+    Attribute, AttributeKind, Env,
+    error::{AlignofSnafu, InvalidAstSnafu, OffsetofSnafu, ParseError, SizeofSnafu},
+    types::source::parse_integer,
+};
+
+/// Base class subobject layout of a record, following the Itanium C++ ABI. libclang does not
+/// expose base class offsets, so they are reconstructed from the field offsets it does expose.
+pub(crate) struct RecordLayout<'tu> {
+    /// Direct bases in declaration order
+    pub bases: Vec<BaseLayout<'tu>>,
+    /// Nearly empty virtual base which is the primary base, if no non-virtual base is dynamic
+    pub primary_virtual_base: Option<clang::Entity<'tu>>,
+    /// All virtual bases in the hierarchy, with offsets in a complete object of this record
+    pub virtual_bases: Vec<BaseLayout<'tu>>,
+    nvsize: usize,
+    nvalign: usize,
+}
+
+pub(crate) struct BaseLayout<'tu> {
+    /// Base specifier of a direct base, `None` for indirect virtual bases
+    pub specifier: Option<clang::Entity<'tu>>,
+    pub decl: clang::Entity<'tu>,
+    pub offset: usize,
+    pub is_virtual: bool,
+    /// Whether the base is the primary base of a class in the hierarchy, and shares its vtable
+    /// pointer instead of being allocated separately
+    pub is_primary: bool,
+}
+
+/// Layouts of the records in a hierarchy, so that each base is only laid out once
+#[derive(Default)]
+pub(crate) struct LayoutCache<'tu> {
+    layouts: HashMap<clang::Entity<'tu>, Rc<RecordLayout<'tu>>>,
+}
+
+impl<'tu> LayoutCache<'tu> {
+    pub fn get(
+        &mut self,
+        env: &Env,
+        decl: &clang::Entity<'tu>,
+    ) -> Result<Rc<RecordLayout<'tu>>, ParseError> {
+        let key = decl.get_canonical_entity();
+        if let Some(layout) = self.layouts.get(&key) {
+            return Ok(layout.clone());
+        }
+        let layout = Rc::new(RecordLayout::new(env, self, decl)?);
+        self.layouts.insert(key, layout.clone());
+        Ok(layout)
+    }
+}
+
+impl<'tu> RecordLayout<'tu> {
+    fn new(
+        env: &Env,
+        layouts: &mut LayoutCache<'tu>,
+        decl: &clang::Entity<'tu>,
+    ) -> Result<Self, ParseError> {
+        let direct_bases = direct_bases(decl)?;
+        let mut virtual_bases = Vec::new();
+        collect_virtual_bases(decl, &mut virtual_bases)?;
+        let mut indirect_primary_bases = Vec::new();
+        if !virtual_bases.is_empty() {
+            collect_indirect_primary_bases(env, layouts, decl, &mut indirect_primary_bases)?;
+        }
+
+        let mut primary_base = None;
+        for (index, (_, base, is_virtual)) in direct_bases.iter().enumerate() {
+            if !is_virtual && is_dynamic(base)? {
+                primary_base = Some(index);
+                break;
+            }
+        }
+        let mut primary_virtual_base = None;
+        if primary_base.is_none() && !virtual_bases.is_empty() {
+            let mut first_nearly_empty = None;
+            primary_virtual_base = select_primary_virtual_base(
+                env,
+                layouts,
+                decl,
+                &indirect_primary_bases,
+                &mut first_nearly_empty,
+            )?
+            .or(first_nearly_empty);
+        }
+
+        let record_is_dynamic = is_dynamic(decl)?;
+        let mut dsize = 0;
+        let mut nvalign = 1;
+        let mut bases = Vec::with_capacity(direct_bases.len());
+        if let Some(index) = primary_base {
+            let (specifier, base, _) = &direct_bases[index];
+            let base_layout = layouts.get(env, base)?;
+            dsize = base_layout.nvsize;
+            nvalign = base_layout.nvalign;
+            bases.push((
+                index,
+                BaseLayout {
+                    specifier: Some(*specifier),
+                    decl: *base,
+                    offset: 0,
+                    is_virtual: false,
+                    is_primary: true,
+                },
+            ));
+        } else if let Some(base) = &primary_virtual_base {
+            // The primary virtual base is allocated first, so it shares the vtable pointer
+            let base_layout = layouts.get(env, base)?;
+            dsize = base_layout.nvsize;
+            nvalign = base_layout.nvalign;
+        } else if record_is_dynamic {
+            dsize = env.word_size().bytes();
+            nvalign = env.word_size().bytes();
+        }
+
+        for (index, (specifier, base, is_virtual)) in direct_bases.iter().enumerate() {
+            if Some(index) == primary_base {
+                continue;
+            }
+            let offset = if *is_virtual || is_empty(base)? {
+                // Virtual bases are placed below, empty bases at offset 0
+                0
+            } else {
+                let base_layout = layouts.get(env, base)?;
+                let offset = dsize.next_multiple_of(base_layout.nvalign);
+                dsize = offset + base_layout.nvsize;
+                nvalign = nvalign.max(base_layout.nvalign);
+                offset
+            };
+            bases.push((
+                index,
+                BaseLayout {
+                    specifier: Some(*specifier),
+                    decl: *base,
+                    offset,
+                    is_virtual: *is_virtual,
+                    is_primary: false,
+                },
+            ));
+        }
+        bases.sort_by_key(|(index, _)| *index);
+        let mut bases = bases.into_iter().map(|(_, base)| base).collect::<Vec<_>>();
+
+        for field in decl.get_children() {
+            if field.get_kind() != clang::EntityKind::FieldDecl {
+                continue;
+            }
+            let offset = field.get_offset_of_field().map_err(|error| {
+                OffsetofSnafu {
+                    field_name: field.get_name().unwrap_or_default(),
+                    struct_name: decl.get_name().unwrap_or_default(),
+                    error,
+                }
+                .build()
+            })?;
+            let ty = field.get_type().ok_or_else(|| {
+                InvalidAstSnafu { message: format!("Field without type: {field:?}") }.build()
+            })?;
+            let end = match field.get_bit_field_width() {
+                Some(width) => offset + width,
+                None => offset + ty.get_sizeof().unwrap_or(0) * 8,
+            };
+            dsize = dsize.max(end.div_ceil(8));
+            nvalign = nvalign.max(ty.get_alignof().unwrap_or(1));
+        }
+
+        let record_type = decl.get_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("Record without type: {decl:?}") }.build()
+        })?;
+
+        let (nvsize, nvalign) = if virtual_bases.is_empty() {
+            let alignment = record_type.get_alignof().map_err(|error| {
+                AlignofSnafu { type_name: record_type.get_display_name(), error }.build()
+            })?;
+            if record_type.is_pod() {
+                // POD types never have their tail padding reused
+                let size = record_type.get_sizeof().map_err(|error| {
+                    SizeofSnafu { type_name: record_type.get_display_name(), error }.build()
+                })?;
+                (size, alignment)
+            } else {
+                (dsize, alignment)
+            }
+        } else {
+            (dsize, nvalign)
+        };
+
+        let mut virtual_base_layouts = Vec::with_capacity(virtual_bases.len());
+        for base in virtual_bases {
+            let is_primary = primary_virtual_base.is_some_and(|p| same_record(&p, &base))
+                || indirect_primary_bases.iter().any(|b| same_record(b, &base));
+            let offset = if is_primary || is_empty(&base)? {
+                // Primary bases are placed below, empty bases at offset 0
+                0
+            } else {
+                let base_layout = layouts.get(env, &base)?;
+                let offset = dsize.next_multiple_of(base_layout.nvalign);
+                dsize = offset + base_layout.nvsize;
+                offset
+            };
+            virtual_base_layouts.push(BaseLayout {
+                specifier: None,
+                decl: base,
+                offset,
+                is_virtual: true,
+                is_primary,
+            });
+        }
+        if !indirect_primary_bases.is_empty() {
+            Self::place_indirect_primary_bases(
+                env,
+                layouts,
+                &bases,
+                primary_virtual_base.as_ref(),
+                &mut virtual_base_layouts,
+            )?;
+        }
+        for base in bases.iter_mut().filter(|b| b.is_virtual) {
+            if let Some(virtual_base) =
+                virtual_base_layouts.iter().find(|v| same_record(&v.decl, &base.decl))
+            {
+                base.offset = virtual_base.offset;
+                base.is_primary = virtual_base.is_primary;
+            }
+        }
+
+        Ok(Self {
+            bases,
+            primary_virtual_base,
+            virtual_bases: virtual_base_layouts,
+            nvsize,
+            nvalign,
+        })
+    }
+
+    /// Places each virtual base which is the primary base of another base class at the offset of
+    /// the first subobject, in inheritance graph order, which has it as primary base
+    fn place_indirect_primary_bases(
+        env: &Env,
+        layouts: &mut LayoutCache<'tu>,
+        bases: &[BaseLayout<'tu>],
+        primary_virtual_base: Option<&clang::Entity<'tu>>,
+        virtual_bases: &mut [BaseLayout<'tu>],
+    ) -> Result<(), ParseError> {
+        let position = |virtual_bases: &[BaseLayout<'tu>], decl: &clang::Entity<'tu>| {
+            virtual_bases.iter().position(|v| same_record(&v.decl, decl))
+        };
+        let mut placed = virtual_bases.iter().map(|base| !base.is_primary).collect::<Vec<_>>();
+        let mut visited = vec![false; virtual_bases.len()];
+        // The record itself claims its primary virtual base, which is at offset 0
+        if let Some(index) = primary_virtual_base.and_then(|p| position(virtual_bases, p)) {
+            placed[index] = true;
+        }
+
+        let mut pending = Vec::new();
+        let mut deferred = Vec::new();
+        let push_bases = |pending: &mut Vec<_>,
+                          deferred: &mut Vec<_>,
+                          bases: &[BaseLayout<'tu>],
+                          offset: usize| {
+            for base in bases.iter().rev() {
+                if base.is_virtual {
+                    deferred.push(base.decl);
+                } else {
+                    pending.push((base.decl, offset + base.offset));
+                }
+            }
+        };
+        push_bases(&mut pending, &mut deferred, bases, 0);
+        loop {
+            while let Some((class, offset)) = pending.pop() {
+                let layout = layouts.get(env, &class)?;
+                if let Some(index) =
+                    layout.primary_virtual_base.and_then(|p| position(virtual_bases, &p))
+                    && !placed[index]
+                {
+                    virtual_bases[index].offset = offset;
+                    placed[index] = true;
+                }
+                push_bases(&mut pending, &mut deferred, &layout.bases, offset);
+            }
+
+            // Virtual bases are visited once, after their offset is known
+            let next = deferred.iter().find_map(|decl| {
+                position(virtual_bases, decl).filter(|index| placed[*index] && !visited[*index])
+            });
+            let Some(index) = next else {
+                break;
+            };
+            visited[index] = true;
+            pending.push((virtual_bases[index].decl, virtual_bases[index].offset));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn same_record(a: &clang::Entity, b: &clang::Entity) -> bool {
+    a.get_canonical_entity() == b.get_canonical_entity()
+}
+
+/// Returns the direct bases of a record as (specifier, definition, is_virtual)
+pub(crate) fn direct_bases<'tu>(
+    decl: &clang::Entity<'tu>,
+) -> Result<Vec<(clang::Entity<'tu>, clang::Entity<'tu>, bool)>, ParseError> {
+    let mut bases = Vec::new();
+    for child in decl.get_children() {
+        if child.get_kind() != clang::EntityKind::BaseSpecifier {
+            continue;
+        }
+        let base_type = child.get_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("BaseSpecifier without type: {child:?}") }.build()
+        })?;
+        let base_decl = base_type.get_canonical_type().get_declaration().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("BaseSpecifier without declaration: {child:?}") }
+                .build()
+        })?;
+        let base_decl = base_decl.get_definition().unwrap_or(base_decl);
+        bases.push((child, base_decl, child.is_virtual_base()));
+    }
+    Ok(bases)
+}
+
+/// Collects the virtual bases which are the primary base of a direct or indirect base of `decl`
+fn collect_indirect_primary_bases<'tu>(
+    env: &Env,
+    layouts: &mut LayoutCache<'tu>,
+    decl: &clang::Entity<'tu>,
+    primary_bases: &mut Vec<clang::Entity<'tu>>,
+) -> Result<(), ParseError> {
+    for (_, base, _) in direct_bases(decl)? {
+        let mut base_virtual_bases = Vec::new();
+        collect_virtual_bases(&base, &mut base_virtual_bases)?;
+        if base_virtual_bases.is_empty() {
+            continue;
+        }
+        if let Some(primary) = layouts.get(env, &base)?.primary_virtual_base
+            && !primary_bases.iter().any(|p| same_record(p, &primary))
+        {
+            primary_bases.push(primary);
+        }
+        collect_indirect_primary_bases(env, layouts, &base, primary_bases)?;
+    }
+    Ok(())
+}
+
+/// Returns the first nearly empty virtual base in inheritance graph order which is not the primary
+/// base of another base class. The first nearly empty virtual base is stored as a fallback.
+fn select_primary_virtual_base<'tu>(
+    env: &Env,
+    layouts: &mut LayoutCache<'tu>,
+    decl: &clang::Entity<'tu>,
+    indirect_primary_bases: &[clang::Entity<'tu>],
+    first_nearly_empty: &mut Option<clang::Entity<'tu>>,
+) -> Result<Option<clang::Entity<'tu>>, ParseError> {
+    for (_, base, is_virtual) in direct_bases(decl)? {
+        if is_virtual && is_nearly_empty(env, layouts, &base)? {
+            if !indirect_primary_bases.iter().any(|p| same_record(p, &base)) {
+                return Ok(Some(base));
+            }
+            first_nearly_empty.get_or_insert(base);
+        }
+        let primary = select_primary_virtual_base(
+            env,
+            layouts,
+            &base,
+            indirect_primary_bases,
+            first_nearly_empty,
+        )?;
+        if primary.is_some() {
+            return Ok(primary);
+        }
+    }
+    Ok(None)
+}
+
+fn collect_virtual_bases<'tu>(
+    decl: &clang::Entity<'tu>,
+    virtual_bases: &mut Vec<clang::Entity<'tu>>,
+) -> Result<(), ParseError> {
+    for (_, base, is_virtual) in direct_bases(decl)? {
+        if is_virtual && !virtual_bases.iter().any(|v| same_record(v, &base)) {
+            virtual_bases.push(base);
+        }
+        collect_virtual_bases(&base, virtual_bases)?;
+    }
+    Ok(())
+}
+
+/// Whether a record needs a vtable pointer
+pub(crate) fn is_dynamic(decl: &clang::Entity) -> Result<bool, ParseError> {
+    let has_virtual_methods = decl.get_children().iter().any(|c| {
+        matches!(c.get_kind(), clang::EntityKind::Method | clang::EntityKind::Destructor)
+            && c.is_virtual_method()
+    });
+    if has_virtual_methods {
+        return Ok(true);
+    }
+    for (_, base, is_virtual) in direct_bases(decl)? {
+        if is_virtual || is_dynamic(&base)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Whether a record is dynamic and holds nothing but its vtable pointer
+fn is_nearly_empty<'tu>(
+    env: &Env,
+    layouts: &mut LayoutCache<'tu>,
+    decl: &clang::Entity<'tu>,
+) -> Result<bool, ParseError> {
+    Ok(is_dynamic(decl)? && layouts.get(env, decl)?.nvsize == env.word_size().bytes())
+}
+
+/// Whether a record is empty for layout purposes
+pub(crate) fn is_empty(decl: &clang::Entity) -> Result<bool, ParseError> {
+    if is_dynamic(decl)? {
+        return Ok(false);
+    }
+    let has_fields = decl.get_children().iter().any(|c| {
+        c.get_kind() == clang::EntityKind::FieldDecl && c.get_bit_field_width() != Some(0)
+    });
+    if has_fields {
+        return Ok(false);
+    }
+    for (_, base, _) in direct_bases(decl)? {
+        if !is_empty(&base)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Largest alignment in bytes requested by `aligned`/`alignas` attributes
+fn explicit_alignment(attributes: &[Attribute]) -> Option<usize> {
+    attributes
+        .iter()
+        .filter(|a| a.kind() == &AttributeKind::Aligned)
+        .filter_map(|a| a.arguments().first()?.parse::<usize>().ok())
+        .max()
+}
+
+/// Returns the alignment in bytes of a field's type, and the alignment the field would have if no
+/// `#pragma pack` was in effect
+pub(crate) fn unpacked_field_alignment(
+    field: &clang::Entity,
+    record_is_packed: bool,
+) -> Result<(usize, usize), ParseError> {
+    let ty = field.get_type().ok_or_else(|| {
+        InvalidAstSnafu { message: format!("Field without type: {field:?}") }.build()
+    })?;
+    let attributes = Attribute::parse_all(field);
+    let is_packed =
+        record_is_packed || attributes.iter().any(|a| a.kind() == &AttributeKind::Packed);
+    let natural = ty
+        .get_alignof()
+        .map_err(|error| AlignofSnafu { type_name: ty.get_display_name(), error }.build())?;
+    let unpacked = if is_packed { 1 } else { natural };
+    let unpacked =
+        explicit_alignment(&attributes).map_or(unpacked, |explicit| unpacked.max(explicit));
+    Ok((natural, unpacked))
+}
+
+/// Returns the `#pragma pack` value in effect for a record. Clang attaches an implicit attribute
+/// to records declared under the pragma, so the value is read from the directive it points at.
+///
+/// `fields` holds the offset in bits and unpacked alignment in bytes of each non-bitfield member.
+pub(crate) fn record_pack(
+    env: &Env,
+    decl: &clang::Entity,
+    alignment: usize,
+    fields: &[(usize, usize)],
+) -> Result<Option<usize>, ParseError> {
+    let Some(attribute) = decl.get_children().into_iter().find(Attribute::is_pragma_pack) else {
+        return Ok(None);
+    };
+    match pragma_pack_value(&attribute) {
+        Some(pack) => Ok(Some(pack)),
+        // The value isn't a literal, e.g. it comes from a macro, so fall back to the layout
+        None => infer_pack(env, decl, alignment, fields),
+    }
+}
+
+/// Reads the value of `pack(N)`, `pack(push, N)`, `pack(push, label, N)` or `pack(pop, N)`
+fn pragma_pack_value(attribute: &clang::Entity) -> Option<usize> {
+    let location = attribute.get_location()?.get_file_location();
+    let contents = location.file?.get_contents()?;
+    let arguments = contents
+        .get(location.offset as usize..)?
+        .strip_prefix("pack")?
+        .trim_start()
+        .strip_prefix('(')?;
+    let arguments = &arguments[..arguments.find(')')?];
+    arguments.split(',').map(str::trim).find_map(parse_integer).map(|pack| pack as usize)
+}
+
+/// Infers the `#pragma pack` value from how the record's alignment and field offsets deviate
+/// from the unpacked layout. Packing which has no effect on the layout is not detected.
+fn infer_pack(
+    env: &Env,
+    decl: &clang::Entity,
+    alignment: usize,
+    fields: &[(usize, usize)],
+) -> Result<Option<usize>, ParseError> {
+    let attributes = Attribute::parse_all(decl);
+    if explicit_alignment(&attributes).is_none() {
+        let mut unpacked_alignment = fields.iter().map(|(_, align)| *align).max().unwrap_or(1);
+        if is_dynamic(decl)? {
+            unpacked_alignment = unpacked_alignment.max(env.word_size().bytes());
+        }
+        for (_, base, _) in direct_bases(decl)? {
+            if let Some(Ok(base_alignment)) = base.get_type().map(|ty| ty.get_alignof()) {
+                unpacked_alignment = unpacked_alignment.max(base_alignment);
+            }
+        }
+        return Ok((alignment < unpacked_alignment).then_some(alignment));
+    }
+
+    // The record alignment is raised by the attribute, so only misaligned fields reveal packing
+    Ok(fields
+        .iter()
+        .map(|(offset, align)| (offset / 8, *align))
+        .filter(|(offset, align)| offset % align != 0)
+        .map(|(offset, _)| 1 << offset.trailing_zeros())
+        .min())
+}
@@ -0,0 +1,41 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// Position of a declaration in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    file: PathBuf,
+    line: u32,
+    column: u32,
+}
+
+impl SourceLocation {
+    pub(crate) fn of(node: &clang::Entity) -> Option<Self> {
+        Self::at(node.get_location()?)
+    }
+
+    pub(crate) fn at(location: clang::source::SourceLocation) -> Option<Self> {
+        let location = location.get_file_location();
+        Some(Self { file: location.file?.get_path(), line: location.line, column: location.column })
+    }
+
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file.display(), self.line, self.column)
+    }
+}
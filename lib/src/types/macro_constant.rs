@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use crate::{
+    ConstantValue, Env, SourceLocation, Types,
+    types::{
+        macro_expr::{self, MacroToken},
+        source::join_tokens,
+    },
+};
+
+/// Object-like macro whose body is a constant expression, such as `#define MAX_ACTORS 64`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroConstant {
+    name: String,
+    /// Body of the macro as written in the source
+    body: String,
+    value: ConstantValue,
+    location: SourceLocation,
+}
+
+/// Object-like macro definition which may be a constant, see [`macro_expr::evaluate`]
+pub(crate) struct MacroDefinition {
+    name: String,
+    body: String,
+    tokens: Vec<MacroToken>,
+    location: SourceLocation,
+    in_main_file: bool,
+}
+
+impl MacroDefinition {
+    pub(crate) fn new(node: &clang::Entity) -> Option<Self> {
+        if node.is_function_like_macro() || node.is_builtin_macro() {
+            return None;
+        }
+        let name = node.get_name()?;
+        let tokens = node.get_range()?.tokenize();
+        let body_tokens = tokens.get(1..)?;
+        let body = join_tokens(body_tokens);
+        let tokens = body_tokens
+            .iter()
+            .map(|token| MacroToken { kind: token.get_kind(), spelling: token.get_spelling() })
+            .collect();
+        let in_main_file = node.get_location()?.is_in_main_file();
+        Some(Self { name, body, tokens, location: SourceLocation::of(node)?, in_main_file })
+    }
+
+    /// Evaluates the body of the definition at `index` as a constant expression, expanding the
+    /// other macros it references
+    pub(crate) fn evaluate(
+        env: &Env,
+        types: &Types,
+        definitions: &[Self],
+        index: usize,
+    ) -> Option<ConstantValue> {
+        macro_expr::evaluate(env, types, definitions, index)
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn tokens(&self) -> &[MacroToken] {
+        &self.tokens
+    }
+
+    /// Whether the macro is defined in the parsed file rather than in a header it includes
+    pub(crate) fn is_in_main_file(&self) -> bool {
+        self.in_main_file
+    }
+
+    /// Whether the macro expands to nothing, like include guards and feature flags
+    pub(crate) fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub(crate) fn into_constant(self, value: ConstantValue) -> MacroConstant {
+        MacroConstant { name: self.name, body: self.body, value, location: self.location }
+    }
+}
+
+impl MacroConstant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn value(&self) -> &ConstantValue {
+        &self.value
+    }
+
+    pub fn location(&self) -> &SourceLocation {
+        &self.location
+    }
+}
+
+impl Display for MacroConstant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#define {} {} // = {}", self.name, self.body, self.value)
+    }
+}
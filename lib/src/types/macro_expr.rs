@@ -0,0 +1,682 @@
+use clang::token::TokenKind;
+
+use crate::{ConstantValue, Env, MacroDefinition, TypeKind, Types, WordSize};
+
+/// Token of a macro body, kept after the translation unit is gone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MacroToken {
+    pub kind: TokenKind,
+    pub spelling: String,
+}
+
+/// Upper bound on the number of tokens a macro may expand to
+const MAX_EXPANDED_TOKENS: usize = 4096;
+
+/// Evaluates a macro body as a C constant expression, following the preprocessor's expansion
+/// rules for object-like macros and the usual arithmetic conversions. Other macros are looked up
+/// in `definitions`, preferring the last definition before the one being evaluated, so that
+/// redefined macros keep the value they had at that point.
+pub(crate) fn evaluate(
+    env: &Env,
+    types: &Types,
+    definitions: &[MacroDefinition],
+    index: usize,
+) -> Option<ConstantValue> {
+    let evaluator = Evaluator { env, types, definitions, index };
+    let mut active = vec![index];
+    let mut tokens = Vec::new();
+    evaluator.expand(definitions[index].tokens(), &mut active, &mut tokens)?;
+
+    let mut parser = ExprParser { evaluator: &evaluator, tokens: &tokens, position: 0 };
+    let expr = parser.conditional()?;
+    if parser.position != tokens.len() {
+        return None;
+    }
+    match evaluator.eval(&expr)? {
+        Value::Int(value, ty) if ty.signed => Some(ConstantValue::Signed(value as i64)),
+        Value::Int(value, _) => Some(ConstantValue::Unsigned(value as u64)),
+        Value::Float(value) => Some(ConstantValue::Float(value)),
+        Value::String(value) => Some(ConstantValue::String(value)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IntType {
+    bits: u32,
+    signed: bool,
+    /// Conversion rank among types of the same width, e.g. `long long` is above `long`
+    rank: u8,
+}
+
+const INT: IntType = IntType { bits: 32, signed: true, rank: 1 };
+
+impl IntType {
+    fn long(env: &Env) -> Self {
+        let bits = match env.word_size() {
+            WordSize::Size64 => 64,
+            WordSize::Size16 | WordSize::Size32 => 32,
+        };
+        IntType { bits, signed: true, rank: 2 }
+    }
+
+    fn long_long() -> Self {
+        IntType { bits: 64, signed: true, rank: 3 }
+    }
+
+    fn unsigned(self) -> Self {
+        IntType { signed: false, ..self }
+    }
+
+    fn order(&self) -> (u32, u8) {
+        (self.bits, self.rank)
+    }
+
+    /// Types narrower than `int` are promoted to `int`, which holds all of their values
+    fn promote(self) -> Self {
+        if self.bits < INT.bits { INT } else { self }
+    }
+
+    /// Common type of the operands of a binary operator
+    fn common(a: Self, b: Self) -> Self {
+        let (a, b) = (a.promote(), b.promote());
+        if a.signed == b.signed {
+            return if a.order() >= b.order() { a } else { b };
+        }
+        let (signed, unsigned) = if a.signed { (a, b) } else { (b, a) };
+        if unsigned.order() >= signed.order() {
+            unsigned
+        } else if signed.bits > unsigned.bits {
+            signed
+        } else {
+            signed.unsigned()
+        }
+    }
+
+    /// Truncates a value to the width of the type
+    fn wrap(&self, value: i128) -> i128 {
+        let shift = 128 - self.bits;
+        if self.signed {
+            (value << shift) >> shift
+        } else {
+            ((value as u128) << shift >> shift) as i128
+        }
+    }
+
+    fn fits(&self, value: u128) -> bool {
+        let bits = if self.signed { self.bits - 1 } else { self.bits };
+        value >> bits == 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Int(i128, IntType),
+    Float(f64),
+    String(String),
+}
+
+impl Value {
+    fn int(value: i128, ty: IntType) -> Self {
+        Value::Int(ty.wrap(value), ty)
+    }
+
+    fn bool(value: bool) -> Self {
+        Value::Int(value.into(), INT)
+    }
+
+    fn is_true(&self) -> Option<bool> {
+        match self {
+            Value::Int(value, _) => Some(*value != 0),
+            Value::Float(value) => Some(*value != 0.0),
+            Value::String(_) => None,
+        }
+    }
+
+    fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Int(value, _) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            Value::String(_) => None,
+        }
+    }
+}
+
+/// Type named in a cast
+#[derive(Debug, Clone, Copy)]
+enum CastType {
+    Int(IntType),
+    Bool,
+    Float,
+    Double,
+}
+
+#[derive(Debug)]
+enum Expr {
+    Value(Value),
+    Cast(CastType, Box<Expr>),
+    Unary(String, Box<Expr>),
+    Binary(String, Box<Expr>, Box<Expr>),
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+struct Evaluator<'a> {
+    env: &'a Env,
+    types: &'a Types,
+    definitions: &'a [MacroDefinition],
+    /// Index of the definition being evaluated
+    index: usize,
+}
+
+impl Evaluator<'_> {
+    /// Finds the definition in effect for a macro referenced by the definition being evaluated
+    fn lookup(&self, name: &str) -> Option<usize> {
+        let (before, after) = self.definitions.split_at(self.index);
+        before.iter().rposition(|d| d.name() == name).or_else(|| {
+            after.iter().position(|d| d.name() == name).map(|position| self.index + position)
+        })
+    }
+
+    /// Replaces macro names by their bodies. Like the preprocessor, a macro is not expanded again
+    /// within its own expansion.
+    fn expand(
+        &self,
+        tokens: &[MacroToken],
+        active: &mut Vec<usize>,
+        expanded: &mut Vec<MacroToken>,
+    ) -> Option<()> {
+        for token in tokens {
+            let definition = (token.kind == TokenKind::Identifier)
+                .then(|| self.lookup(&token.spelling))
+                .flatten()
+                .filter(|index| !active.contains(index));
+            match definition {
+                Some(index) => {
+                    active.push(index);
+                    self.expand(self.definitions[index].tokens(), active, expanded)?;
+                    active.pop();
+                }
+                None => expanded.push(token.clone()),
+            }
+            if expanded.len() > MAX_EXPANDED_TOKENS {
+                return None;
+            }
+        }
+        Some(())
+    }
+
+    /// Value of an identifier which is not a macro, such as an enum constant
+    fn identifier(&self, name: &str) -> Option<Value> {
+        if let Some((enum_decl, constant)) = self.types.get_enum_constant(name) {
+            let bits = (enum_decl.size() * 8).clamp(8, 64) as u32;
+            let ty = IntType { bits, signed: constant.is_signed(), rank: 1 }.promote();
+            let value = if constant.is_signed() {
+                constant.value().into()
+            } else {
+                constant.unsigned_value().into()
+            };
+            return Some(Value::int(value, ty));
+        }
+        let constant = self.types.get_constant(name)?;
+        match (constant.value(), self.cast_type(constant.kind())) {
+            (ConstantValue::Signed(value), Some(CastType::Int(ty))) => {
+                Some(Value::int((*value).into(), ty.promote()))
+            }
+            (ConstantValue::Unsigned(value), Some(CastType::Int(ty))) => {
+                Some(Value::int((*value).into(), ty.promote()))
+            }
+            (ConstantValue::Signed(value), _) => {
+                Some(Value::int((*value).into(), IntType::long_long()))
+            }
+            (ConstantValue::Unsigned(value), _) => {
+                Some(Value::int((*value).into(), IntType::long_long().unsigned()))
+            }
+            (ConstantValue::Float(value), _) => Some(Value::Float(*value)),
+            (ConstantValue::String(_), _) => None,
+        }
+    }
+
+    /// Type to cast to for a type name found in the parsed types, such as `u8`
+    fn cast_type(&self, kind: &TypeKind) -> Option<CastType> {
+        let int = |bits, signed| Some(CastType::Int(IntType { bits, signed, rank: 1 }));
+        match kind {
+            TypeKind::U8 => int(8, false),
+            TypeKind::S8 => int(8, true),
+            TypeKind::U16 | TypeKind::Char16 => int(16, false),
+            TypeKind::S16 => int(16, true),
+            TypeKind::U32 | TypeKind::Char32 => int(32, false),
+            TypeKind::S32 => int(32, true),
+            TypeKind::U64 => Some(CastType::Int(IntType::long_long().unsigned())),
+            TypeKind::S64 => Some(CastType::Int(IntType::long_long())),
+            TypeKind::USize { size } => int(*size as u32 * 8, false),
+            TypeKind::SSize { size } | TypeKind::WChar { size } => int(*size as u32 * 8, true),
+            TypeKind::Bool => Some(CastType::Bool),
+            TypeKind::F32 => Some(CastType::Float),
+            TypeKind::F64 | TypeKind::LongDouble { .. } => Some(CastType::Double),
+            TypeKind::Typedef(typedef) => self.cast_type(typedef.underlying_type()),
+            TypeKind::Named(name) => self.cast_type(self.types.get(name)?),
+            _ => None,
+        }
+    }
+
+    fn eval(&self, expr: &Expr) -> Option<Value> {
+        match expr {
+            Expr::Value(value) => Some(value.clone()),
+            Expr::Cast(ty, operand) => {
+                let value = self.eval(operand)?;
+                match (ty, value) {
+                    (CastType::Int(ty), Value::Int(value, _)) => {
+                        Some(Value::int(ty.wrap(value), ty.promote()))
+                    }
+                    (CastType::Int(ty), Value::Float(value)) => {
+                        Some(Value::int(ty.wrap(value as i128), ty.promote()))
+                    }
+                    (CastType::Bool, value) => Some(Value::bool(value.is_true()?)),
+                    (CastType::Float, value) => Some(Value::Float(value.as_float()? as f32 as f64)),
+                    (CastType::Double, value) => Some(Value::Float(value.as_float()?)),
+                    (_, Value::String(_)) => None,
+                }
+            }
+            Expr::Unary(op, operand) => {
+                let value = self.eval(operand)?;
+                match (op.as_str(), value) {
+                    ("!", value) => Some(Value::bool(!value.is_true()?)),
+                    ("+", Value::Int(value, ty)) => Some(Value::int(value, ty.promote())),
+                    ("-", Value::Int(value, ty)) => Some(Value::int(-value, ty.promote())),
+                    ("~", Value::Int(value, ty)) => Some(Value::int(!value, ty.promote())),
+                    ("+", Value::Float(value)) => Some(Value::Float(value)),
+                    ("-", Value::Float(value)) => Some(Value::Float(-value)),
+                    _ => None,
+                }
+            }
+            Expr::Binary(op, left, right) => match op.as_str() {
+                "&&" => {
+                    let value = self.eval(left)?.is_true()? && self.eval(right)?.is_true()?;
+                    Some(Value::bool(value))
+                }
+                "||" => {
+                    let value = self.eval(left)?.is_true()? || self.eval(right)?.is_true()?;
+                    Some(Value::bool(value))
+                }
+                "," => {
+                    self.eval(left)?;
+                    self.eval(right)
+                }
+                op => self.binary(op, self.eval(left)?, self.eval(right)?),
+            },
+            Expr::Conditional(condition, then, otherwise) => {
+                // Only the selected operand is evaluated, the other one only affects the type
+                let condition = self.eval(condition)?.is_true()?;
+                let (selected, other) =
+                    if condition { (then, otherwise) } else { (otherwise, then) };
+                match (self.eval(selected)?, self.eval(other)) {
+                    (Value::Int(value, a), Some(Value::Int(_, b))) => {
+                        Some(Value::int(value, IntType::common(a, b)))
+                    }
+                    (Value::Int(value, _), Some(Value::Float(_))) => {
+                        Some(Value::Float(value as f64))
+                    }
+                    (value, _) => Some(value),
+                }
+            }
+        }
+    }
+
+    fn binary(&self, op: &str, left: Value, right: Value) -> Option<Value> {
+        if let (Value::Int(a, a_ty), Value::Int(b, b_ty)) = (&left, &right) {
+            let (a, b) = (*a, *b);
+            if matches!(op, "<<" | ">>") {
+                let ty = a_ty.promote();
+                let shift = u32::try_from(b).ok().filter(|shift| *shift < ty.bits)?;
+                let value = if op == "<<" { a << shift } else { a >> shift };
+                return Some(Value::int(value, ty));
+            }
+            let ty = IntType::common(*a_ty, *b_ty);
+            let (a, b) = (ty.wrap(a), ty.wrap(b));
+            let value = match op {
+                "*" => a.wrapping_mul(b),
+                "/" => a.checked_div(b)?,
+                "%" => a.checked_rem(b)?,
+                "+" => a.wrapping_add(b),
+                "-" => a.wrapping_sub(b),
+                "&" => a & b,
+                "^" => a ^ b,
+                "|" => a | b,
+                "<" => return Some(Value::bool(a < b)),
+                ">" => return Some(Value::bool(a > b)),
+                "<=" => return Some(Value::bool(a <= b)),
+                ">=" => return Some(Value::bool(a >= b)),
+                "==" => return Some(Value::bool(a == b)),
+                "!=" => return Some(Value::bool(a != b)),
+                _ => return None,
+            };
+            return Some(Value::int(value, ty));
+        }
+
+        let (a, b) = (left.as_float()?, right.as_float()?);
+        match op {
+            "*" => Some(Value::Float(a * b)),
+            "/" => Some(Value::Float(a / b)),
+            "+" => Some(Value::Float(a + b)),
+            "-" => Some(Value::Float(a - b)),
+            "<" => Some(Value::bool(a < b)),
+            ">" => Some(Value::bool(a > b)),
+            "<=" => Some(Value::bool(a <= b)),
+            ">=" => Some(Value::bool(a >= b)),
+            "==" => Some(Value::bool(a == b)),
+            "!=" => Some(Value::bool(a != b)),
+            _ => None,
+        }
+    }
+}
+
+struct ExprParser<'a> {
+    evaluator: &'a Evaluator<'a>,
+    tokens: &'a [MacroToken],
+    position: usize,
+}
+
+/// Binary operators from lowest to highest precedence
+const BINARY_OPERATORS: [&[&str]; 10] = [
+    &["||"],
+    &["&&"],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+impl ExprParser<'_> {
+    fn peek(&self) -> Option<&MacroToken> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&MacroToken> {
+        let token = self.tokens.get(self.position)?;
+        self.position += 1;
+        Some(token)
+    }
+
+    fn eat(&mut self, punctuation: &str) -> bool {
+        let matches = self
+            .peek()
+            .is_some_and(|t| t.kind == TokenKind::Punctuation && t.spelling == punctuation);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn expression(&mut self) -> Option<Expr> {
+        let mut expr = self.conditional()?;
+        while self.eat(",") {
+            expr = Expr::Binary(",".to_string(), Box::new(expr), Box::new(self.conditional()?));
+        }
+        Some(expr)
+    }
+
+    fn conditional(&mut self) -> Option<Expr> {
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Some(condition);
+        }
+        let then = self.expression()?;
+        if !self.eat(":") {
+            return None;
+        }
+        let otherwise = self.conditional()?;
+        Some(Expr::Conditional(Box::new(condition), Box::new(then), Box::new(otherwise)))
+    }
+
+    fn binary(&mut self, level: usize) -> Option<Expr> {
+        let Some(operators) = BINARY_OPERATORS.get(level) else {
+            return self.unary();
+        };
+        let mut expr = self.binary(level + 1)?;
+        while let Some(op) = self
+            .peek()
+            .filter(|t| {
+                t.kind == TokenKind::Punctuation && operators.contains(&t.spelling.as_str())
+            })
+            .map(|t| t.spelling.clone())
+        {
+            self.position += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.binary(level + 1)?));
+        }
+        Some(expr)
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        let token = self.peek()?;
+        if token.kind == TokenKind::Punctuation
+            && matches!(token.spelling.as_str(), "+" | "-" | "~" | "!")
+        {
+            let op = token.spelling.clone();
+            self.position += 1;
+            return Some(Expr::Unary(op, Box::new(self.unary()?)));
+        }
+        if let Some(ty) = self.cast() {
+            return Some(Expr::Cast(ty, Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    /// Parses a parenthesized type name such as `(unsigned char)` or `(u8)`, and leaves the
+    /// tokens untouched if there is none
+    fn cast(&mut self) -> Option<CastType> {
+        let start = self.position;
+        if !self.eat("(") {
+            return None;
+        }
+        let mut words = Vec::new();
+        while let Some(token) = self.peek().filter(|t| t.kind != TokenKind::Punctuation) {
+            words.push(token.clone());
+            self.position += 1;
+        }
+        let ty = if self.eat(")") { self.cast_type(&words) } else { None };
+        if ty.is_none() {
+            self.position = start;
+        }
+        ty
+    }
+
+    fn cast_type(&self, words: &[MacroToken]) -> Option<CastType> {
+        if let [word] = words
+            && word.kind == TokenKind::Identifier
+        {
+            return self.evaluator.cast_type(self.evaluator.types.get(&word.spelling)?);
+        }
+
+        let mut signed = None;
+        let mut longs = 0;
+        let mut base = None;
+        for word in words {
+            if word.kind != TokenKind::Keyword {
+                return None;
+            }
+            match word.spelling.as_str() {
+                "signed" => signed = Some(true),
+                "unsigned" => signed = Some(false),
+                "long" => longs += 1,
+                "const" | "volatile" => {}
+                "char" | "short" | "int" | "float" | "double" | "bool" | "_Bool" => {
+                    base = Some(word.spelling.as_str())
+                }
+                _ => return None,
+            }
+        }
+        let int = match base {
+            Some("float") => return Some(CastType::Float),
+            Some("double") => return Some(CastType::Double),
+            Some("bool" | "_Bool") => return Some(CastType::Bool),
+            Some("char") => IntType {
+                bits: 8,
+                signed: signed.unwrap_or(self.evaluator.env.signed_char()),
+                rank: 1,
+            },
+            Some("short") => IntType { bits: 16, signed: true, rank: 1 },
+            Some("int") | None if longs == 1 => IntType::long(self.evaluator.env),
+            Some("int") | None if longs >= 2 => IntType::long_long(),
+            Some("int") => INT,
+            None if signed.is_some() => INT,
+            _ => return None,
+        };
+        Some(CastType::Int(IntType { signed: signed.unwrap_or(int.signed), ..int }))
+    }
+
+    fn primary(&mut self) -> Option<Expr> {
+        let token = self.next()?.clone();
+        match token.kind {
+            TokenKind::Punctuation if token.spelling == "(" => {
+                let expr = self.expression()?;
+                self.eat(")").then_some(expr)
+            }
+            TokenKind::Keyword => match token.spelling.as_str() {
+                "true" => Some(Expr::Value(Value::bool(true))),
+                "false" => Some(Expr::Value(Value::bool(false))),
+                _ => None,
+            },
+            TokenKind::Identifier => Some(Expr::Value(self.evaluator.identifier(&token.spelling)?)),
+            TokenKind::Literal if is_string_literal(&token.spelling) => {
+                // Adjacent string literals are concatenated
+                let mut value = string_literal(&token.spelling)?;
+                while let Some(next) = self
+                    .peek()
+                    .filter(|t| t.kind == TokenKind::Literal && is_string_literal(&t.spelling))
+                {
+                    value.push_str(&string_literal(&next.spelling)?);
+                    self.position += 1;
+                }
+                Some(Expr::Value(Value::String(value)))
+            }
+            TokenKind::Literal => Some(Expr::Value(self.literal(&token.spelling)?)),
+            _ => None,
+        }
+    }
+
+    fn literal(&self, spelling: &str) -> Option<Value> {
+        if spelling.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            number_literal(self.evaluator.env, spelling)
+        } else {
+            char_literal(self.evaluator.env, spelling)
+        }
+    }
+}
+
+fn is_string_literal(spelling: &str) -> bool {
+    spelling.trim_start_matches(['L', 'u', 'U', '8']).starts_with('"')
+}
+
+/// Parses an integer or floating point literal, giving integers the first type of the C standard's
+/// list for their suffix and base which can represent them
+fn number_literal(env: &Env, spelling: &str) -> Option<Value> {
+    let spelling = spelling.replace('\'', "");
+    let lower = spelling.to_ascii_lowercase();
+    let is_hex = lower.starts_with("0x");
+    if (!is_hex && lower.contains(['.', 'e'])) || (is_hex && lower.contains('p')) {
+        let digits = lower.trim_end_matches(['f', 'l']);
+        if is_hex {
+            return None;
+        }
+        let value = digits.parse::<f64>().ok()?;
+        let is_float = lower.ends_with('f');
+        return Some(Value::Float(if is_float { value as f32 as f64 } else { value }));
+    }
+
+    let digits = lower.trim_end_matches(['u', 'l']);
+    let suffix = &lower[digits.len()..];
+    let (digits, radix) = if let Some(digits) = digits.strip_prefix("0x") {
+        (digits, 16)
+    } else if let Some(digits) = digits.strip_prefix("0b") {
+        (digits, 2)
+    } else if digits.len() > 1
+        && let Some(digits) = digits.strip_prefix('0')
+    {
+        (digits, 8)
+    } else {
+        (digits, 10)
+    };
+    let value = u128::from_str_radix(digits, radix).ok()?;
+
+    let is_unsigned = suffix.contains('u');
+    let longs = suffix.matches('l').count();
+    let candidates = [INT, IntType::long(env), IntType::long_long()];
+    let candidates = &candidates[longs.min(2)..];
+    let ty = candidates.iter().find_map(|ty| {
+        if is_unsigned {
+            ty.unsigned().fits(value).then(|| ty.unsigned())
+        } else if ty.fits(value) {
+            Some(*ty)
+        } else if radix != 10 && ty.unsigned().fits(value) {
+            Some(ty.unsigned())
+        } else {
+            None
+        }
+    })?;
+    Some(Value::int(value as i128, ty))
+}
+
+/// Parses a character literal such as `'A'` or `'\n'`, which has type `int`
+fn char_literal(env: &Env, spelling: &str) -> Option<Value> {
+    let is_plain = spelling.starts_with('\'');
+    let contents = spelling.trim_start_matches(['L', 'u', 'U', '8']);
+    let contents = contents.strip_prefix('\'')?.strip_suffix('\'')?;
+    let [code] = unescape(contents)?[..] else {
+        return None;
+    };
+    let mut value = i128::from(code);
+    if is_plain && env.signed_char() && code > 0x7f {
+        value = i128::from(code as u8 as i8);
+    }
+    Some(Value::int(value, INT))
+}
+
+fn string_literal(spelling: &str) -> Option<String> {
+    let contents = spelling.trim_start_matches(['L', 'u', 'U', '8']);
+    let contents = contents.strip_prefix('"')?.strip_suffix('"')?;
+    unescape(contents)?.into_iter().map(char::from_u32).collect()
+}
+
+/// Decodes the escape sequences of a character or string literal
+fn unescape(contents: &str) -> Option<Vec<u32>> {
+    let mut codes = Vec::new();
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            codes.push(c as u32);
+            continue;
+        }
+        let code = match chars.next()? {
+            'n' => 0x0a,
+            't' => 0x09,
+            'r' => 0x0d,
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0c,
+            'v' => 0x0b,
+            'x' => {
+                let mut digits = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_hexdigit) {
+                    digits.push(digit);
+                }
+                u32::from_str_radix(&digits, 16).ok()?
+            }
+            digit @ '0'..='7' => {
+                let mut digits = digit.to_string();
+                while digits.len() < 3
+                    && let Some(digit) = chars.next_if(|c| ('0'..='7').contains(c))
+                {
+                    digits.push(digit);
+                }
+                u32::from_str_radix(&digits, 8).ok()?
+            }
+            c @ ('\\' | '\'' | '"' | '?') => c as u32,
+            _ => return None,
+        };
+        codes.push(code);
+    }
+    Some(codes)
+}
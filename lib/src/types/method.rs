@@ -0,0 +1,159 @@
+use std::fmt::Display;
+
+use crate::{
+    Access, Attribute, DocComment, Env, TypeKind, Types,
+    error::{InvalidAstSnafu, ParseError},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Method {
+    name: String,
+    kind: MethodKind,
+    signature: TypeKind,
+    access: Access,
+    is_static: bool,
+    is_const: bool,
+    is_virtual: bool,
+    is_pure_virtual: bool,
+    is_override: bool,
+    is_deleted: bool,
+    attributes: Vec<Attribute>,
+    comment: Option<DocComment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodKind {
+    Method,
+    Constructor,
+    Destructor,
+    Conversion,
+}
+
+impl Method {
+    pub fn new(env: &Env, types: &Types, node: &clang::Entity) -> Result<Self, ParseError> {
+        let kind = match node.get_kind() {
+            clang::EntityKind::Method => MethodKind::Method,
+            clang::EntityKind::Constructor => MethodKind::Constructor,
+            clang::EntityKind::Destructor => MethodKind::Destructor,
+            clang::EntityKind::ConversionFunction => MethodKind::Conversion,
+            _ => {
+                return InvalidAstSnafu { message: format!("Expected method, found: {node:?}") }
+                    .fail();
+            }
+        };
+
+        let name = node.get_name().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("Method without name: {node:?}") }.build()
+        })?;
+        let ty = node.get_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("Method without type: {node:?}") }.build()
+        })?;
+        let signature = TypeKind::new(env, types, ty)?;
+
+        let is_override =
+            node.get_children().iter().any(|c| c.get_kind() == clang::EntityKind::OverrideAttr);
+
+        Ok(Self {
+            name,
+            kind,
+            signature,
+            access: Access::of(node),
+            is_static: node.is_static_method(),
+            is_const: node.is_const_method(),
+            is_virtual: node.is_virtual_method(),
+            is_pure_virtual: node.is_pure_virtual_method(),
+            is_override,
+            is_deleted: Self::has_deleted_definition(node),
+            attributes: Attribute::parse_all(node),
+            comment: DocComment::of(node),
+        })
+    }
+
+    /// libclang has no query for deleted functions, so look for `= delete` before the body
+    pub(crate) fn has_deleted_definition(node: &clang::Entity) -> bool {
+        let Some(range) = node.get_range() else {
+            return false;
+        };
+        let tokens = range
+            .tokenize()
+            .into_iter()
+            .map(|t| t.get_spelling())
+            .take_while(|t| t != "{")
+            .collect::<Vec<_>>();
+        tokens.windows(2).any(|w| w[0] == "=" && w[1] == "delete")
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> MethodKind {
+        self.kind
+    }
+
+    pub fn signature(&self) -> &TypeKind {
+        &self.signature
+    }
+
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    pub fn is_const(&self) -> bool {
+        self.is_const
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
+    pub fn is_pure_virtual(&self) -> bool {
+        self.is_pure_virtual
+    }
+
+    pub fn is_override(&self) -> bool {
+        self.is_override
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}{}{}: {}{}",
+            self.access,
+            if self.is_static { "static " } else { "" },
+            if self.is_virtual { "virtual " } else { "" },
+            self.name,
+            self.signature,
+            if self.is_const { " const" } else { "" },
+        )?;
+        if self.is_override {
+            write!(f, " override")?;
+        }
+        if self.is_pure_virtual {
+            write!(f, " = 0")?;
+        }
+        if self.is_deleted {
+            write!(f, " = delete")?;
+        }
+        Ok(())
+    }
+}
@@ -1,23 +1,77 @@
+mod access;
+mod attribute;
+mod bitfield;
+mod calling_convention;
+mod comment;
+mod completeness;
+mod constant;
 mod enum_decl;
 mod field;
+mod function;
+mod global_constant;
+mod global_variable;
+mod layout;
+mod location;
+mod macro_constant;
+mod macro_expr;
+mod method;
+mod record;
+mod scope;
+mod source;
+mod static_member;
+mod storage_class;
 mod struct_decl;
+mod traits;
 mod type_kind;
 mod typedef;
 mod union_decl;
+mod vtable;
 
 use indexmap::IndexMap;
 
+pub use access::Access;
+pub use attribute::{Attribute, AttributeKind};
+pub use bitfield::BitFieldUnit;
+pub use calling_convention::CallingConvention;
+pub use comment::DocComment;
+pub use completeness::Completeness;
+pub use constant::ConstantValue;
 pub use enum_decl::{EnumConstant, EnumDecl};
 pub use field::Field;
+pub use function::{Function, Parameter};
+pub use global_constant::GlobalConstant;
+pub use global_variable::GlobalVariable;
+pub use location::SourceLocation;
+pub use macro_constant::MacroConstant;
+pub(crate) use macro_constant::MacroDefinition;
+pub use method::{Method, MethodKind};
+pub use record::RecordFields;
 use snafu::Snafu;
-pub use struct_decl::{StructDecl, StructField};
+pub(crate) use source::parse_integer;
+pub use static_member::StaticMember;
+pub use storage_class::StorageClass;
+pub use struct_decl::{BaseType, StructDecl, StructField};
+pub use traits::RecordTraits;
 pub use type_kind::TypeKind;
 pub use typedef::Typedef;
 pub use union_decl::UnionDecl;
+pub use vtable::{
+    ReturnAdjustment, SecondaryVTable, Thunk, VTable, VTableEntry, VTableEntryKind,
+    VirtualBaseOffset,
+};
 
 #[derive(Default)]
 pub struct Types {
     types: IndexMap<String, TypeKind>,
+    /// Enums without a name, which are only used for their constants
+    anonymous_enums: Vec<EnumDecl>,
+    macro_constants: IndexMap<String, MacroConstant>,
+    /// Constants at namespace scope by qualified name
+    constants: IndexMap<String, GlobalConstant>,
+    /// Variables at namespace scope by qualified name
+    variables: IndexMap<String, GlobalVariable>,
+    /// Free functions by mangled name, so that overloads are kept apart
+    functions: IndexMap<String, Function>,
 }
 
 #[derive(Debug, Snafu)]
@@ -64,6 +118,107 @@ impl Types {
         }
     }
 
+    pub fn add_anonymous_enum(&mut self, enum_decl: EnumDecl) {
+        if !self.anonymous_enums.contains(&enum_decl) {
+            self.anonymous_enums.push(enum_decl);
+        }
+    }
+
+    pub fn anonymous_enums(&self) -> &[EnumDecl] {
+        &self.anonymous_enums
+    }
+
+    pub fn add_macro_constant(&mut self, constant: MacroConstant) {
+        self.macro_constants.entry(constant.name().to_string()).or_insert(constant);
+    }
+
+    /// Object-like macros which evaluate to integer, float or string constants
+    pub fn macro_constants(&self) -> impl Iterator<Item = &MacroConstant> {
+        self.macro_constants.values()
+    }
+
+    pub fn get_macro_constant(&self, name: &str) -> Option<&MacroConstant> {
+        self.macro_constants.get(name)
+    }
+
+    pub fn add_constant(&mut self, constant: GlobalConstant) {
+        self.constants.entry(constant.qualified_name().to_string()).or_insert(constant);
+    }
+
+    /// Constant variables and anonymous enum constants at namespace scope
+    pub fn constants(&self) -> impl Iterator<Item = &GlobalConstant> {
+        self.constants.values()
+    }
+
+    /// Looks up a constant by qualified name, or by unqualified name if there is no exact match
+    pub fn get_constant(&self, name: &str) -> Option<&GlobalConstant> {
+        self.constants.get(name).or_else(|| self.constants.values().find(|c| c.name() == name))
+    }
+
+    /// Adds a variable, replacing an earlier declaration of it if this one is its definition
+    pub fn add_variable(&mut self, variable: GlobalVariable) {
+        match self.variables.entry(variable.qualified_name().to_string()) {
+            indexmap::map::Entry::Occupied(mut entry) => {
+                if !entry.get().is_definition() && variable.is_definition() {
+                    entry.insert(variable);
+                }
+            }
+            indexmap::map::Entry::Vacant(entry) => {
+                entry.insert(variable);
+            }
+        }
+    }
+
+    /// Variables at namespace scope, including constants and `extern` declarations. Variables of
+    /// unsupported types such as `__int128` are left out.
+    pub fn variables(&self) -> impl Iterator<Item = &GlobalVariable> {
+        self.variables.values()
+    }
+
+    /// Looks up a variable by qualified name, or by unqualified name if there is no exact match
+    pub fn get_variable(&self, name: &str) -> Option<&GlobalVariable> {
+        self.variables.get(name).or_else(|| self.variables.values().find(|v| v.name() == name))
+    }
+
+    /// Adds a function, replacing an earlier declaration of it if this one is its definition
+    pub fn add_function(&mut self, function: Function) {
+        let key = function.mangled_name().unwrap_or(function.qualified_name()).to_string();
+        match self.functions.entry(key) {
+            indexmap::map::Entry::Occupied(mut entry) => {
+                if !entry.get().is_definition() && function.is_definition() {
+                    entry.insert(function);
+                }
+            }
+            indexmap::map::Entry::Vacant(entry) => {
+                entry.insert(function);
+            }
+        }
+    }
+
+    /// Free functions at namespace scope in declaration order. Functions taking or returning
+    /// unsupported types such as `__int128` are left out.
+    pub fn functions(&self) -> impl Iterator<Item = &Function> {
+        self.functions.values()
+    }
+
+    /// Looks up a function by qualified name, or by unqualified name if there is no exact match.
+    /// Returns the first overload if there are several, see [`Self::get_overloads`].
+    pub fn get_function(&self, name: &str) -> Option<&Function> {
+        self.functions
+            .values()
+            .find(|f| f.qualified_name() == name)
+            .or_else(|| self.functions.values().find(|f| f.name() == name))
+    }
+
+    /// Lists the functions with a qualified name in declaration order
+    pub fn get_overloads(&self, qualified_name: &str) -> Vec<&Function> {
+        self.functions.values().filter(|f| f.qualified_name() == qualified_name).collect()
+    }
+
+    pub fn get_function_by_mangled_name(&self, mangled_name: &str) -> Option<&Function> {
+        self.functions.get(mangled_name)
+    }
+
     pub fn types(&self) -> impl Iterator<Item = &TypeKind> {
         self.types.values()
     }
@@ -80,7 +235,135 @@ impl Types {
         self.types.get(name)
     }
 
+    /// Returns an enum by name, looking through typedefs
+    pub fn get_enum(&self, name: &str) -> Option<&EnumDecl> {
+        let mut ty = self.get(name)?;
+        loop {
+            match ty {
+                TypeKind::Enum(enum_decl) => return Some(enum_decl),
+                TypeKind::Typedef(typedef) => ty = typedef.underlying_type(),
+                TypeKind::Named(name) => ty = self.get(name)?,
+                _ => return None,
+            }
+        }
+    }
+
+    /// Iterates over all enums, named ones first
+    pub fn enums(&self) -> impl Iterator<Item = &EnumDecl> {
+        self.types
+            .values()
+            .filter_map(|ty| match ty {
+                TypeKind::Enum(enum_decl) => Some(enum_decl),
+                _ => None,
+            })
+            .chain(&self.anonymous_enums)
+    }
+
+    /// Finds the enum which declares a constant, including anonymous enums
+    pub fn get_enum_constant(&self, name: &str) -> Option<(&EnumDecl, &EnumConstant)> {
+        self.enums().find_map(|enum_decl| Some((enum_decl, enum_decl.get(name)?)))
+    }
+
+    /// Lists the constants of an enum which have a value, in declaration order. There can be
+    /// several if some constants are aliases of others.
+    pub fn get_enum_constants_by_value(&self, enum_name: &str, value: i64) -> Vec<&EnumConstant> {
+        self.get_enum(enum_name).map(|e| e.get_all_by_value(value)).unwrap_or_default()
+    }
+
+    /// Formats a value of an enum, see [`EnumDecl::format_value`]
+    pub fn format_enum_value(&self, enum_name: &str, value: i64) -> Option<String> {
+        Some(self.get_enum(enum_name)?.format_value(value))
+    }
+
+    /// Lists all base types of a struct or class in layout order, including indirect and virtual
+    /// bases. Bases are named by their declaration, as in `Types`.
+    pub fn ancestors(&self, name: &str) -> Vec<&str> {
+        let mut ancestors = Vec::new();
+        let Some(struct_decl) = self.get(name).and_then(|ty| ty.as_struct(self)) else {
+            return ancestors;
+        };
+        self.collect_non_virtual_ancestors(struct_decl, &mut ancestors);
+        for base in struct_decl.virtual_bases() {
+            if !ancestors.contains(&base.decl_name()) {
+                ancestors.push(base.decl_name());
+            }
+            if let Some(base_decl) = self.get(base.decl_name()).and_then(|ty| ty.as_struct(self)) {
+                self.collect_non_virtual_ancestors(base_decl, &mut ancestors);
+            }
+        }
+        ancestors
+    }
+
+    fn collect_non_virtual_ancestors<'a>(
+        &'a self,
+        struct_decl: &'a StructDecl,
+        ancestors: &mut Vec<&'a str>,
+    ) {
+        let mut bases =
+            struct_decl.base_types().iter().filter(|b| !b.is_virtual()).collect::<Vec<_>>();
+        bases.sort_by_key(|b| b.offset());
+        for base in bases {
+            if !ancestors.contains(&base.decl_name()) {
+                ancestors.push(base.decl_name());
+            }
+            if let Some(base_decl) = self.get(base.decl_name()).and_then(|ty| ty.as_struct(self)) {
+                self.collect_non_virtual_ancestors(base_decl, ancestors);
+            }
+        }
+    }
+
+    /// Lists the structs and classes which directly inherit from the given type
+    pub fn direct_derived_types(&self, name: &str) -> Vec<&str> {
+        self.types
+            .iter()
+            .filter(|(_, ty)| {
+                ty.as_struct(self)
+                    .is_some_and(|s| s.base_types().iter().any(|base| base.decl_name() == name))
+            })
+            .map(|(derived, _)| derived.as_str())
+            .collect()
+    }
+
+    /// Lists the structs and classes which directly or indirectly inherit from the given type
+    pub fn derived_types(&self, name: &str) -> Vec<&str> {
+        self.types
+            .keys()
+            .map(|derived| derived.as_str())
+            .filter(|derived| self.is_derived_from(derived, name))
+            .collect()
+    }
+
+    /// Returns the root of the hierarchy of a struct or class by following its first base type
+    pub fn hierarchy_root<'a>(&'a self, name: &'a str) -> &'a str {
+        let mut root = name;
+        while let Some(base) =
+            self.get(root).and_then(|ty| ty.as_struct(self)).and_then(|s| s.base_types().first())
+        {
+            root = base.decl_name();
+        }
+        root
+    }
+
+    pub fn is_derived_from(&self, derived: &str, base: &str) -> bool {
+        derived != base && self.ancestors(derived).contains(&base)
+    }
+
     pub fn extend(&mut self, other: Types) -> Result<(), ExtendTypesError> {
+        for enum_decl in other.anonymous_enums {
+            self.add_anonymous_enum(enum_decl);
+        }
+        for constant in other.macro_constants.into_values() {
+            self.add_macro_constant(constant);
+        }
+        for constant in other.constants.into_values() {
+            self.add_constant(constant);
+        }
+        for variable in other.variables.into_values() {
+            self.add_variable(variable);
+        }
+        for function in other.functions.into_values() {
+            self.add_function(function);
+        }
         for (name, value) in other.types {
             match self.types.entry(name.clone()) {
                 indexmap::map::Entry::Occupied(mut entry) => {
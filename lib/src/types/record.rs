@@ -0,0 +1,32 @@
+use crate::{StructField, TypeKind, Types};
+
+/// Fields of a struct, class or union, for code which handles all records alike
+pub trait RecordFields {
+    /// Fields declared directly in the record, with their offsets in bits
+    fn fields(&self) -> &[StructField];
+
+    /// Looks up a field by name, with its offset within the record
+    fn get_field(&self, types: &Types, name: &str) -> Option<StructField>;
+}
+
+/// Looks up a field in the anonymous struct and union members of a record, which are accessed as
+/// if they were members of the record itself. `offset` is the offset in bytes of the record.
+pub(crate) fn find_anonymous_field(
+    fields: &[StructField],
+    types: &Types,
+    name: &str,
+    offset: usize,
+) -> Option<StructField> {
+    fields.iter().filter(|f| f.name().is_none()).find_map(|f| {
+        let field_offset = offset + f.offset_bytes();
+        match f.kind() {
+            TypeKind::Struct(decl) | TypeKind::Class(decl) if decl.name().is_none() => {
+                decl.find_field(types, name, field_offset, &[])
+            }
+            TypeKind::Union(decl) if decl.name().is_none() => {
+                decl.find_field(types, name, field_offset)
+            }
+            _ => None,
+        }
+    })
+}
@@ -0,0 +1,28 @@
+/// Returns the name of a declaration prefixed by its enclosing namespaces and records, such as
+/// `game::Actor::kMaxHp`. Anonymous scopes are left out.
+pub(crate) fn qualified_name(node: &clang::Entity) -> Option<String> {
+    let mut parts = vec![node.get_name()?];
+    let mut parent = node.get_semantic_parent();
+    while let Some(scope) = parent {
+        match scope.get_kind() {
+            clang::EntityKind::TranslationUnit => break,
+            clang::EntityKind::Namespace
+            | clang::EntityKind::StructDecl
+            | clang::EntityKind::ClassDecl
+            | clang::EntityKind::UnionDecl => {
+                if let Some(name) = scope.get_name() {
+                    parts.push(name);
+                }
+            }
+            clang::EntityKind::EnumDecl if scope.is_scoped() => {
+                if let Some(name) = scope.get_name() {
+                    parts.push(name);
+                }
+            }
+            _ => {}
+        }
+        parent = scope.get_semantic_parent();
+    }
+    parts.reverse();
+    Some(parts.join("::"))
+}
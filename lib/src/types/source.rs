@@ -0,0 +1,53 @@
+/// Joins tokens back into source text, separating them by a single space wherever the source had
+/// whitespace between them
+pub(crate) fn join_tokens(tokens: &[clang::token::Token]) -> String {
+    let mut text = String::new();
+    let mut previous_end = None;
+    for token in tokens {
+        let range = token.get_range();
+        let start = range.get_start().get_file_location().offset;
+        if previous_end.is_some_and(|end| end < start) {
+            text.push(' ');
+        }
+        text.push_str(&token.get_spelling());
+        previous_end = Some(range.get_end().get_file_location().offset);
+    }
+    text
+}
+
+/// Returns identifiers in `tokens` which don't name anything referenced by `node` or its
+/// descendants. These come from macro expansions, as libclang only sees the expanded code.
+pub(crate) fn macro_names(node: &clang::Entity, tokens: &[clang::token::Token]) -> Vec<String> {
+    let mut referenced = Vec::new();
+    node.visit_children(|child, _| {
+        if (child.is_reference() || child.get_kind() == clang::EntityKind::DeclRefExpr)
+            && let Some(name) = child.get_reference().and_then(|r| r.get_name())
+        {
+            referenced.push(name);
+        }
+        clang::EntityVisitResult::Recurse
+    });
+
+    let mut macros = Vec::<String>::new();
+    for token in tokens {
+        if token.get_kind() != clang::token::TokenKind::Identifier {
+            continue;
+        }
+        let name = token.get_spelling();
+        if !referenced.contains(&name) && !macros.contains(&name) {
+            macros.push(name);
+        }
+    }
+    macros
+}
+
+/// Parses a decimal, hexadecimal (`0x`) or binary (`0b`) integer literal without suffixes
+pub(crate) fn parse_integer(text: &str) -> Option<u64> {
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        u64::from_str_radix(binary, 2).ok()
+    } else {
+        text.parse().ok()
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use crate::{
+    Access, ConstantValue, DocComment, Env, TypeKind, Types,
+    error::{InvalidAstSnafu, ParseError},
+};
+
+/// Static data member of a struct or class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticMember {
+    name: String,
+    kind: TypeKind,
+    access: Access,
+    constant: bool,
+    value: Option<ConstantValue>,
+    comment: Option<DocComment>,
+}
+
+impl StaticMember {
+    pub fn new(env: &Env, types: &Types, node: &clang::Entity) -> Result<Self, ParseError> {
+        if node.get_kind() != clang::EntityKind::VarDecl {
+            return InvalidAstSnafu { message: format!("Expected VarDecl, found: {node:?}") }
+                .fail();
+        }
+
+        let name = node.get_name().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("VarDecl without name: {node:?}") }.build()
+        })?;
+        let ty = node.get_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("VarDecl without type: {node:?}") }.build()
+        })?;
+
+        Ok(Self {
+            name,
+            kind: TypeKind::new(env, types, ty)?,
+            access: Access::of(node),
+            constant: ty.is_const_qualified(),
+            value: ConstantValue::evaluate(node),
+            comment: DocComment::of(node),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> &TypeKind {
+        &self.kind
+    }
+
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    pub fn constant(&self) -> bool {
+        self.constant
+    }
+
+    /// Value of the in-class initializer, if it is a constant expression
+    pub fn value(&self) -> Option<&ConstantValue> {
+        self.value.as_ref()
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+}
+
+impl Display for StaticMember {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} static {}: {}{}",
+            self.access,
+            self.name,
+            if self.constant { "const " } else { "" },
+            self.kind
+        )?;
+        if let Some(value) = &self.value {
+            write!(f, " = {value}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt::Display;
+
+/// Storage class specifier of a variable or function declaration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageClass {
+    /// No storage class specifier
+    None,
+    Static,
+    Extern,
+    /// `__private_extern__`, external linkage but not visible outside the linked image
+    PrivateExtern,
+}
+
+impl StorageClass {
+    pub(crate) fn of(node: &clang::Entity) -> Self {
+        node.get_storage_class().map(Self::from).unwrap_or(StorageClass::None)
+    }
+}
+
+impl From<clang::StorageClass> for StorageClass {
+    fn from(value: clang::StorageClass) -> Self {
+        match value {
+            clang::StorageClass::Static => StorageClass::Static,
+            clang::StorageClass::Extern => StorageClass::Extern,
+            clang::StorageClass::PrivateExtern => StorageClass::PrivateExtern,
+            clang::StorageClass::None
+            | clang::StorageClass::Auto
+            | clang::StorageClass::Register
+            | clang::StorageClass::OpenClWorkGroupLocal => StorageClass::None,
+        }
+    }
+}
+
+impl Display for StorageClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageClass::None => Ok(()),
+            StorageClass::Static => write!(f, "static"),
+            StorageClass::Extern => write!(f, "extern"),
+            StorageClass::PrivateExtern => write!(f, "__private_extern__"),
+        }
+    }
+}
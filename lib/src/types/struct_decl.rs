@@ -1,27 +1,63 @@
 use std::fmt::Display;
 
 use crate::{
-    Env, Field, TypeKind, Types,
+    Access, Attribute, BitFieldUnit, Completeness, DocComment, Env, Field, Method, RecordFields,
+    RecordTraits, StaticMember, TypeKind, Types, VTable,
     error::{
         AlignofSnafu, InvalidAstSnafu, InvalidFieldsSnafu, OffsetofSnafu, ParseError, SizeofSnafu,
         UnsupportedEntitySnafu, UnsupportedTypeSnafu,
     },
+    types::{
+        layout::{LayoutCache, record_pack, same_record, unpacked_field_alignment},
+        record::find_anonymous_field,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StructDecl {
     pub(crate) name: Option<String>,
-    pub(crate) base_types: Vec<String>,
+    pub(crate) base_types: Vec<BaseType>,
+    virtual_bases: Vec<BaseType>,
     pub(crate) fields: Vec<StructField>,
+    static_members: Vec<StaticMember>,
+    methods: Vec<Method>,
+    vtable: Option<VTable>,
     size: usize,
     alignment: usize,
     is_class: bool,
+    traits: RecordTraits,
+    attributes: Vec<Attribute>,
+    pack: Option<usize>,
+    completeness: Completeness,
+    comment: Option<DocComment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseType {
+    name: String,
+    /// Name of the base's declaration, as it is stored in `Types`
+    decl_name: String,
+    /// Display name of the canonical type, which identifies the base however it is spelled
+    canonical_name: String,
+    /// Offset in bytes. For virtual bases, this is the offset within a complete object of the
+    /// derived type.
+    offset: usize,
+    access: Access,
+    is_virtual: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StructField {
     /// Offset in bits
     offset: usize,
+    /// Alignment in bytes, after `#pragma pack` and alignment attributes
+    alignment: usize,
+    /// Alignment in bytes of the field's type
+    natural_alignment: usize,
+    /// Alignment in bytes the field would have without `#pragma pack`
+    unpacked_alignment: usize,
+    bit_field_unit: Option<BitFieldUnit>,
+    access: Access,
     field: Field,
 }
 
@@ -36,24 +72,34 @@ impl StructDecl {
             return InvalidAstSnafu { message: format!("Expected Record, found: {ty:?}") }.fail();
         }
 
-        let mut base_types = Vec::new();
+        let mut static_members = Vec::new();
+        let mut methods = Vec::new();
         let Some(node) = ty.get_declaration() else {
             return InvalidAstSnafu { message: format!("Record type without declaration: {ty:?}") }
                 .fail();
         };
         for child in node.get_children() {
-            if child.get_kind() != clang::EntityKind::BaseSpecifier {
-                continue;
+            match child.get_kind() {
+                clang::EntityKind::VarDecl => {
+                    static_members.push(StaticMember::new(env, types, &child)?);
+                }
+                clang::EntityKind::Method
+                | clang::EntityKind::Constructor
+                | clang::EntityKind::Destructor
+                | clang::EntityKind::ConversionFunction => {
+                    methods.push(Method::new(env, types, &child)?);
+                }
+                _ => {}
             }
-            let base_type = child.get_type().ok_or_else(|| {
-                InvalidAstSnafu { message: format!("BaseSpecifier without type: {child:?}") }
-                    .build()
-            })?;
-            let base_name = base_type.get_display_name();
-            base_types.push(base_name);
         }
 
+        let mut layouts = LayoutCache::default();
+        let (base_types, virtual_bases) = Self::get_base_types(env, &mut layouts, &node)?;
+
         let is_class = node.get_kind() == clang::EntityKind::ClassDecl;
+        let vtable = VTable::new(env, types, &mut layouts, &node)?;
+        let traits = RecordTraits::new(&node, &ty)?;
+        let attributes = Attribute::parse_all(&node);
 
         let display_name = name.as_deref().unwrap_or("<anon>");
 
@@ -90,8 +136,13 @@ impl StructDecl {
         for field in &record_fields {
             match field.get_kind() {
                 clang::EntityKind::FieldDecl => {
-                    let offset = Self::get_offset_of_field(display_name, field)?;
-                    fields.push(StructField { offset, field: Field::new(env, types, field)? });
+                    fields.push(StructField::new(
+                        env,
+                        types,
+                        display_name,
+                        field,
+                        traits.is_packed(),
+                    )?);
                 }
                 _ => {
                     return UnsupportedEntitySnafu {
@@ -106,35 +157,106 @@ impl StructDecl {
             }
         }
 
+        let completeness = Completeness::of(
+            &node,
+            !fields.is_empty() || !base_types.is_empty() || vtable.is_some(),
+        );
+
         let size = ty.get_sizeof().or_else(|e| {
-            if record_fields.is_empty() {
+            if completeness == Completeness::ForwardDeclared {
                 Ok(1)
             } else {
                 SizeofSnafu { type_name: display_name.to_string(), error: e }.fail()
             }
         })?;
         let alignment = ty.get_alignof().or_else(|e| {
-            if record_fields.is_empty() {
+            if completeness == Completeness::ForwardDeclared {
                 Ok(1)
             } else {
                 AlignofSnafu { type_name: display_name.to_string(), error: e }.fail()
             }
         })?;
 
-        Ok(Self { name, base_types, fields, size, alignment, is_class })
-    }
+        let pack = StructField::apply_pack(env, &node, alignment, &mut fields)?;
 
-    fn get_offset_of_field(struct_name: &str, node: &clang::Entity) -> Result<usize, ParseError> {
-        node.get_offset_of_field().map_err(|e| {
-            OffsetofSnafu {
-                field_name: node.get_name().unwrap_or_default(),
-                struct_name: struct_name.to_string(),
-                error: e,
-            }
-            .build()
+        Ok(Self {
+            name,
+            base_types,
+            virtual_bases,
+            fields,
+            static_members,
+            methods,
+            vtable,
+            size,
+            alignment,
+            is_class,
+            traits,
+            attributes,
+            pack,
+            completeness,
+            comment: DocComment::of(&node),
         })
     }
 
+    fn get_base_types<'tu>(
+        env: &Env,
+        layouts: &mut LayoutCache<'tu>,
+        node: &clang::Entity<'tu>,
+    ) -> Result<(Vec<BaseType>, Vec<BaseType>), ParseError> {
+        if !node.get_children().iter().any(|c| c.get_kind() == clang::EntityKind::BaseSpecifier) {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let layout = layouts.get(env, node)?;
+        let mut base_types = Vec::with_capacity(layout.bases.len());
+        for base in &layout.bases {
+            let specifier = base.specifier.as_ref().ok_or_else(|| {
+                InvalidAstSnafu { message: format!("Base without specifier: {node:?}") }.build()
+            })?;
+            let base_type = specifier.get_type().ok_or_else(|| {
+                InvalidAstSnafu { message: format!("BaseSpecifier without type: {specifier:?}") }
+                    .build()
+            })?;
+            base_types.push(BaseType {
+                name: base_type.get_display_name(),
+                decl_name: base.decl.get_name().unwrap_or_default(),
+                canonical_name: base_type.get_canonical_type().get_display_name(),
+                offset: base.offset,
+                access: Access::of(specifier),
+                is_virtual: base.is_virtual,
+            });
+        }
+
+        let virtual_bases = layout
+            .virtual_bases
+            .iter()
+            .map(|base| {
+                let base_type = base.decl.get_type().ok_or_else(|| {
+                    InvalidAstSnafu { message: format!("Record without type: {:?}", base.decl) }
+                        .build()
+                })?;
+                // Direct virtual bases keep the name and access they were declared with
+                let direct =
+                    layout.bases.iter().zip(&base_types).find(|(direct, _)| {
+                        direct.is_virtual && same_record(&direct.decl, &base.decl)
+                    });
+                Ok(match direct {
+                    Some((_, direct)) => direct.clone(),
+                    None => BaseType {
+                        name: base_type.get_display_name(),
+                        decl_name: base.decl.get_name().unwrap_or_default(),
+                        canonical_name: base_type.get_canonical_type().get_display_name(),
+                        offset: base.offset,
+                        access: Access::Public,
+                        is_virtual: true,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        Ok((base_types, virtual_bases))
+    }
+
     pub fn size(&self) -> usize {
         self.size
     }
@@ -143,30 +265,83 @@ impl StructDecl {
         self.alignment
     }
 
+    pub fn completeness(&self) -> Completeness {
+        self.completeness
+    }
+
+    /// Whether the record is declared but not defined in the parsed headers
     pub fn is_forward_decl(&self) -> bool {
-        self.fields.is_empty()
+        self.completeness == Completeness::ForwardDeclared
     }
 
-    pub fn base_types(&self) -> &[String] {
+    pub fn base_types(&self) -> &[BaseType] {
         &self.base_types
     }
 
+    /// All virtual bases in the hierarchy, including indirect ones
+    pub fn virtual_bases(&self) -> &[BaseType] {
+        &self.virtual_bases
+    }
+
+    /// Data members declared in this struct. The vtable pointer of a dynamic class is not a
+    /// member, see [`Self::vtable_pointer_offset`].
     pub fn fields(&self) -> &[StructField] {
         &self.fields
     }
 
-    pub fn get_field<'a>(&'a self, types: &'a Types, name: &str) -> Option<&'a StructField> {
-        self.fields.iter().find(|f| f.name() == Some(name)).or_else(|| {
-            self.base_types
-                .iter()
-                .filter_map(|base| types.get(base))
-                .filter_map(|base| base.expand_named(types))
-                .filter_map(|base| match base {
-                    TypeKind::Struct(struct_decl) => struct_decl.get_field(types, name),
-                    TypeKind::Class(class_decl) => class_decl.get_field(types, name),
-                    _ => None,
-                })
-                .next()
+    pub fn static_members(&self) -> &[StaticMember] {
+        &self.static_members
+    }
+
+    pub fn get_static_member(&self, name: &str) -> Option<&StaticMember> {
+        self.static_members.iter().find(|m| m.name() == name)
+    }
+
+    pub fn methods(&self) -> &[Method] {
+        &self.methods
+    }
+
+    pub fn get_method(&self, name: &str) -> Option<&Method> {
+        self.methods.iter().find(|m| m.name() == name)
+    }
+
+    pub fn vtable(&self) -> Option<&VTable> {
+        self.vtable.as_ref()
+    }
+
+    /// Offset in bytes of the vtable pointer of a dynamic class. The Itanium C++ ABI always puts
+    /// it at offset 0, where it is shared with the primary base if any.
+    pub fn vtable_pointer_offset(&self) -> Option<usize> {
+        self.vtable.as_ref().map(|_| 0)
+    }
+
+    /// Looks up a field in this struct, its anonymous struct/union members or its bases, with
+    /// its offset within this struct
+    pub fn get_field(&self, types: &Types, name: &str) -> Option<StructField> {
+        self.find_field(types, name, 0, &self.virtual_bases)
+    }
+
+    pub(crate) fn find_field(
+        &self,
+        types: &Types,
+        name: &str,
+        offset: usize,
+        virtual_bases: &[BaseType],
+    ) -> Option<StructField> {
+        if let Some(field) = self.fields.iter().find(|f| f.name() == Some(name)) {
+            return Some(field.shifted(offset));
+        }
+        if let Some(field) = find_anonymous_field(&self.fields, types, name, offset) {
+            return Some(field);
+        }
+        self.base_types.iter().find_map(|base| {
+            let base_offset = if base.is_virtual {
+                virtual_bases.iter().find(|v| v.canonical_name == base.canonical_name)?.offset
+            } else {
+                offset + base.offset
+            };
+            let base_decl = types.get(&base.decl_name)?.as_struct(types)?;
+            base_decl.find_field(types, name, base_offset, virtual_bases)
         })
     }
 
@@ -177,9 +352,108 @@ impl StructDecl {
     pub fn is_class(&self) -> bool {
         self.is_class
     }
+
+    pub fn traits(&self) -> &RecordTraits {
+        &self.traits
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+
+    /// Maximum field alignment in bytes set by `#pragma pack`, `None` if no pragma is in effect
+    pub fn pack(&self) -> Option<usize> {
+        self.pack
+    }
+}
+
+impl RecordFields for StructDecl {
+    fn fields(&self) -> &[StructField] {
+        self.fields()
+    }
+
+    fn get_field(&self, types: &Types, name: &str) -> Option<StructField> {
+        self.get_field(types, name)
+    }
 }
 
 impl StructField {
+    pub(crate) fn new(
+        env: &Env,
+        types: &Types,
+        record_name: &str,
+        node: &clang::Entity,
+        record_is_packed: bool,
+    ) -> Result<Self, ParseError> {
+        let offset = node.get_offset_of_field().map_err(|e| {
+            OffsetofSnafu {
+                field_name: node.get_name().unwrap_or_default(),
+                struct_name: record_name.to_string(),
+                error: e,
+            }
+            .build()
+        })?;
+        let (natural_alignment, unpacked_alignment) =
+            unpacked_field_alignment(node, record_is_packed)?;
+        let field = Field::new(env, types, node)?;
+        let bit_field_unit = match field.bit_field_width() {
+            Some(width) => {
+                let ty = node.get_type().ok_or_else(|| {
+                    InvalidAstSnafu { message: format!("Field without type: {node:?}") }.build()
+                })?;
+                let container_size = ty.get_sizeof().map_err(|error| {
+                    SizeofSnafu { type_name: ty.get_display_name(), error }.build()
+                })?;
+                Some(BitFieldUnit::new(env, offset, width, container_size))
+            }
+            None => None,
+        };
+        Ok(Self {
+            offset,
+            alignment: unpacked_alignment,
+            natural_alignment,
+            unpacked_alignment,
+            bit_field_unit,
+            access: Access::of(node),
+            field,
+        })
+    }
+
+    /// Reads the `#pragma pack` value of a record and lowers the alignment of its fields to it
+    pub(crate) fn apply_pack(
+        env: &Env,
+        record: &clang::Entity,
+        alignment: usize,
+        fields: &mut [StructField],
+    ) -> Result<Option<usize>, ParseError> {
+        let aligned_fields = fields
+            .iter()
+            .filter(|f| f.bit_field_width().is_none())
+            .map(|f| (f.offset, f.unpacked_alignment))
+            .collect::<Vec<_>>();
+        let pack = record_pack(env, record, alignment, &aligned_fields)?;
+        if let Some(pack) = pack {
+            for field in fields {
+                field.alignment = field.unpacked_alignment.min(pack);
+            }
+        }
+        Ok(pack)
+    }
+
+    /// Returns a copy of the field with its offset moved by a number of bytes
+    pub(crate) fn shifted(&self, offset: usize) -> Self {
+        Self {
+            offset: self.offset + offset * 8,
+            bit_field_unit: self.bit_field_unit.map(|unit| unit.shifted(offset)),
+            ..self.clone()
+        }
+    }
+
     pub fn offset_bytes(&self) -> usize {
         self.offset / 8
     }
@@ -192,7 +466,25 @@ impl StructField {
         self.field.name()
     }
 
-    pub fn kind(&self) -> &super::TypeKind {
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    pub fn natural_alignment(&self) -> usize {
+        self.natural_alignment
+    }
+
+    /// Whether `#pragma pack` or a `packed`/`aligned` attribute changed the alignment of the
+    /// field from that of its type
+    pub fn has_alignment_override(&self) -> bool {
+        self.alignment != self.natural_alignment
+    }
+
+    pub fn kind(&self) -> &TypeKind {
         self.field.kind()
     }
 
@@ -208,11 +500,67 @@ impl StructField {
         self.field.bit_field_width()
     }
 
+    /// Storage unit of a bitfield, whose declared type is the field's kind
+    pub fn bit_field_unit(&self) -> Option<&BitFieldUnit> {
+        self.bit_field_unit.as_ref()
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        self.field.attributes()
+    }
+
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.field.comment()
+    }
+
     pub fn size(&self, types: &Types) -> usize {
         self.field.size(types)
     }
 }
 
+impl BaseType {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Unqualified name of the base's declaration, under which it is stored in `Types`. Unlike
+    /// [`BaseType::name`], this does not depend on how the base is spelled.
+    pub fn decl_name(&self) -> &str {
+        &self.decl_name
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+}
+
+impl Display for BaseType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}{} ({:#x})",
+            self.access,
+            if self.is_virtual { "virtual " } else { "" },
+            self.name,
+            self.offset
+        )
+    }
+}
+
+impl Display for StructField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.field)
+    }
+}
+
 impl Display for StructDecl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name.as_deref().unwrap_or("<anon>"))?;
@@ -225,8 +573,17 @@ impl Display for StructDecl {
             }
         }
         writeln!(f, " {{")?;
+        if let Some(offset) = self.vtable_pointer_offset() {
+            writeln!(f, "  ({offset:#x}) __vptr")?;
+        }
         for field in &self.fields {
-            writeln!(f, "  ({:#x}) {}", field.offset_bytes(), field.field)?;
+            writeln!(f, "  ({:#x}) {field}", field.offset_bytes())?;
+        }
+        for static_member in &self.static_members {
+            writeln!(f, "  {static_member}")?;
+        }
+        for method in &self.methods {
+            writeln!(f, "  {method}")?;
         }
         write!(f, "}}")?;
         Ok(())
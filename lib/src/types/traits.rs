@@ -0,0 +1,219 @@
+use crate::{
+    Access, Method,
+    error::ParseError,
+    types::layout::{direct_bases, is_dynamic, is_empty},
+};
+
+/// Properties of a struct or class which affect how it can be laid out, copied and exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RecordTraits {
+    is_packed: bool,
+    has_explicit_alignment: bool,
+    is_polymorphic: bool,
+    is_abstract: bool,
+    is_pod: bool,
+    is_standard_layout: bool,
+    is_trivially_copyable: bool,
+    is_empty: bool,
+}
+
+impl RecordTraits {
+    pub(crate) fn new(node: &clang::Entity, ty: &clang::Type) -> Result<Self, ParseError> {
+        Ok(Self {
+            is_packed: node
+                .get_children()
+                .iter()
+                .any(|c| c.get_kind() == clang::EntityKind::PackedAttr),
+            has_explicit_alignment: has_alignment_attribute(node),
+            is_polymorphic: is_polymorphic(node)?,
+            is_abstract: node.is_abstract_record(),
+            is_pod: ty.is_pod(),
+            is_standard_layout: is_standard_layout(node)?,
+            is_trivially_copyable: is_trivially_copyable(node)?,
+            is_empty: is_empty(node)?,
+        })
+    }
+
+    /// Whether `__attribute__((packed))` is applied to the record
+    pub fn is_packed(&self) -> bool {
+        self.is_packed
+    }
+
+    /// Whether the record has an `alignas` or `__attribute__((aligned))` specifier
+    pub fn has_explicit_alignment(&self) -> bool {
+        self.has_explicit_alignment
+    }
+
+    /// Whether the record declares or inherits a virtual function
+    pub fn is_polymorphic(&self) -> bool {
+        self.is_polymorphic
+    }
+
+    /// Whether the record has a pure virtual function which is not overridden
+    pub fn is_abstract(&self) -> bool {
+        self.is_abstract
+    }
+
+    pub fn is_pod(&self) -> bool {
+        self.is_pod
+    }
+
+    pub fn is_standard_layout(&self) -> bool {
+        self.is_standard_layout
+    }
+
+    pub fn is_trivially_copyable(&self) -> bool {
+        self.is_trivially_copyable
+    }
+
+    /// Whether the record has no non-static data members, virtual functions or virtual bases,
+    /// and all of its bases are empty
+    pub fn is_empty(&self) -> bool {
+        self.is_empty
+    }
+}
+
+fn has_alignment_attribute(node: &clang::Entity) -> bool {
+    node.get_children().iter().any(|c| match c.get_kind() {
+        clang::EntityKind::AlignedAttr => true,
+        clang::EntityKind::UnexposedAttr => c.get_range().is_some_and(|range| {
+            range.tokenize().iter().any(|t| {
+                matches!(
+                    t.get_spelling().as_str(),
+                    "aligned" | "__aligned__" | "alignas" | "_Alignas"
+                )
+            })
+        }),
+        _ => false,
+    })
+}
+
+fn is_polymorphic(node: &clang::Entity) -> Result<bool, ParseError> {
+    let has_virtual_methods = node.get_children().iter().any(|c| {
+        matches!(c.get_kind(), clang::EntityKind::Method | clang::EntityKind::Destructor)
+            && c.is_virtual_method()
+    });
+    if has_virtual_methods {
+        return Ok(true);
+    }
+    for (_, base, _) in direct_bases(node)? {
+        if is_polymorphic(&base)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns the declarations of record types among the non-static data members of a record
+fn field_records<'tu>(node: &clang::Entity<'tu>) -> Vec<clang::Entity<'tu>> {
+    node.get_children()
+        .iter()
+        .filter(|c| c.get_kind() == clang::EntityKind::FieldDecl)
+        .filter_map(|c| {
+            let mut ty = c.get_type()?.get_canonical_type();
+            while let Some(element_type) = ty.get_element_type() {
+                ty = element_type.get_canonical_type();
+            }
+            if ty.get_kind() != clang::TypeKind::Record {
+                return None;
+            }
+            let decl = ty.get_declaration()?;
+            Some(decl.get_definition().unwrap_or(decl))
+        })
+        .collect()
+}
+
+fn has_fields(node: &clang::Entity) -> bool {
+    node.get_children().iter().any(|c| c.get_kind() == clang::EntityKind::FieldDecl)
+}
+
+fn is_standard_layout(node: &clang::Entity) -> Result<bool, ParseError> {
+    if is_dynamic(node)? {
+        return Ok(false);
+    }
+
+    let mut accesses = node
+        .get_children()
+        .into_iter()
+        .filter(|c| c.get_kind() == clang::EntityKind::FieldDecl)
+        .map(|c| Access::of(&c));
+    if let Some(first) = accesses.next()
+        && accesses.any(|access| access != first)
+    {
+        return Ok(false);
+    }
+
+    for field_record in field_records(node) {
+        if !is_standard_layout(&field_record)? {
+            return Ok(false);
+        }
+    }
+
+    // Only one class in the hierarchy may have non-static data members
+    let mut classes_with_fields = usize::from(has_fields(node));
+    for (_, base, _) in direct_bases(node)? {
+        if !is_standard_layout(&base)? {
+            return Ok(false);
+        }
+        if !is_empty(&base)? {
+            classes_with_fields += 1;
+        }
+    }
+    Ok(classes_with_fields <= 1)
+}
+
+/// Whether a method is a copy or move assignment operator, taking `T`, `T&`, `const T&` or `T&&`
+fn is_copy_or_move_assignment(method: &clang::Entity, node: &clang::Entity) -> bool {
+    if method.get_name().as_deref() != Some("operator=") {
+        return false;
+    }
+    let arguments = method.get_type().and_then(|ty| ty.get_argument_types()).unwrap_or_default();
+    let [argument] = arguments.as_slice() else {
+        return false;
+    };
+    let argument = argument.get_canonical_type();
+    let argument = match argument.get_kind() {
+        clang::TypeKind::LValueReference | clang::TypeKind::RValueReference => {
+            let Some(pointee) = argument.get_pointee_type() else {
+                return false;
+            };
+            pointee
+        }
+        _ => argument,
+    };
+    argument
+        .get_declaration()
+        .is_some_and(|decl| decl.get_canonical_entity() == node.get_canonical_entity())
+}
+
+fn is_trivially_copyable(node: &clang::Entity) -> Result<bool, ParseError> {
+    if is_dynamic(node)? {
+        return Ok(false);
+    }
+
+    // User-provided copy/move operations or destructors make a record non-trivial
+    let has_user_provided = node.get_children().iter().any(|c| {
+        let is_special = match c.get_kind() {
+            clang::EntityKind::Constructor => c.is_copy_constructor() || c.is_move_constructor(),
+            clang::EntityKind::Destructor => true,
+            clang::EntityKind::Method => is_copy_or_move_assignment(c, node),
+            _ => false,
+        };
+        is_special && !c.is_defaulted() && !Method::has_deleted_definition(c)
+    });
+    if has_user_provided {
+        return Ok(false);
+    }
+
+    for field_record in field_records(node) {
+        if !is_trivially_copyable(&field_record)? {
+            return Ok(false);
+        }
+    }
+    for (_, base, _) in direct_bases(node)? {
+        if !is_trivially_copyable(&base)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
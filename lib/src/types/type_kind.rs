@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use crate::{
-    EnumDecl, Env, StructDecl, Typedef, Types, UnionDecl,
+    Attribute, DocComment, EnumDecl, Env, RecordFields, StructDecl, Typedef, Types, UnionDecl,
     error::{AlignofSnafu, ParseError, SizeofSnafu, UnsupportedEntitySnafu, UnsupportedTypeSnafu},
 };
 
@@ -38,6 +38,10 @@ pub enum TypeKind {
         size: usize,
         referenced_type: Box<TypeKind>,
     },
+    RValueReference {
+        size: usize,
+        referenced_type: Box<TypeKind>,
+    },
     Pointer {
         size: usize,
         pointee_type: Box<TypeKind>,
@@ -97,7 +101,9 @@ impl TypeKind {
             }),
             clang::TypeKind::Bool => Ok(TypeKind::Bool),
             clang::TypeKind::Void => Ok(TypeKind::Void),
-            clang::TypeKind::LValueReference | clang::TypeKind::Pointer => {
+            clang::TypeKind::LValueReference
+            | clang::TypeKind::RValueReference
+            | clang::TypeKind::Pointer => {
                 let pointee_type = ty.get_pointee_type().ok_or_else(|| {
                     UnsupportedTypeSnafu {
                         message: format!("Pointer type without pointee type: {ty:?}"),
@@ -105,15 +111,25 @@ impl TypeKind {
                     .build()
                 })?;
                 let inner_type = TypeKind::new(env, types, pointee_type)?;
-                let size = ty.get_sizeof().map_err(|e| {
-                    SizeofSnafu { type_name: ty.get_display_name(), error: e }.build()
-                })?;
+                let size = if kind == clang::TypeKind::Pointer {
+                    ty.get_sizeof().map_err(|e| {
+                        SizeofSnafu { type_name: ty.get_display_name(), error: e }.build()
+                    })?
+                } else {
+                    // sizeof on a reference yields the size of the referenced type, which may be
+                    // incomplete, so use the size of the pointer backing the reference instead
+                    env.word_size().bytes()
+                };
                 let pointee_type = Box::new(inner_type);
 
-                if kind == clang::TypeKind::LValueReference {
-                    Ok(TypeKind::Reference { size, referenced_type: pointee_type })
-                } else {
-                    Ok(TypeKind::Pointer { size, pointee_type })
+                match kind {
+                    clang::TypeKind::LValueReference => {
+                        Ok(TypeKind::Reference { size, referenced_type: pointee_type })
+                    }
+                    clang::TypeKind::RValueReference => {
+                        Ok(TypeKind::RValueReference { size, referenced_type: pointee_type })
+                    }
+                    _ => Ok(TypeKind::Pointer { size, pointee_type }),
                 }
             }
             clang::TypeKind::MemberPointer => {
@@ -185,6 +201,30 @@ impl TypeKind {
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(TypeKind::Function { return_type: Box::new(return_type), parameters })
             }
+            clang::TypeKind::FunctionNoPrototype => {
+                // K&R declaration such as `void (*callback)()` in C, whose parameters are unknown
+                let return_type = ty.get_result_type().ok_or_else(|| {
+                    UnsupportedTypeSnafu {
+                        message: format!("FunctionNoPrototype without return type: {ty:?}"),
+                    }
+                    .build()
+                })?;
+                let return_type = TypeKind::new(env, types, return_type)?;
+                Ok(TypeKind::Function {
+                    return_type: Box::new(return_type),
+                    parameters: Vec::new(),
+                })
+            }
+            clang::TypeKind::Auto => {
+                let canonical_type = ty.get_canonical_type();
+                if canonical_type.get_kind() == clang::TypeKind::Auto {
+                    return UnsupportedTypeSnafu {
+                        message: format!("Auto type is not deduced: {ty:?}"),
+                    }
+                    .fail();
+                }
+                TypeKind::new(env, types, canonical_type)
+            }
             clang::TypeKind::Elaborated => {
                 let elaborated_type = ty.get_elaborated_type().ok_or_else(|| {
                     UnsupportedTypeSnafu {
@@ -251,11 +291,12 @@ impl TypeKind {
                     .build()
                 })?;
                 let name = decl.get_name();
-                Ok(TypeKind::Enum(EnumDecl::new(name, &decl)?))
+                Ok(TypeKind::Enum(EnumDecl::new(env, types, name, &decl)?))
             }
-            _ => {
-                panic!("Unsupported type: {:?} for name: {}", ty.get_kind(), ty.get_display_name())
+            _ => UnsupportedTypeSnafu {
+                message: format!("{:?} for name: {}", ty.get_kind(), ty.get_display_name()),
             }
+            .fail(),
         }
     }
 
@@ -275,6 +316,7 @@ impl TypeKind {
             TypeKind::Bool => 1,
             TypeKind::Void => 0,
             TypeKind::Reference { size, .. } => *size,
+            TypeKind::RValueReference { size, .. } => *size,
             TypeKind::Pointer { size, .. } => *size,
             TypeKind::MemberPointer { size, .. } => *size,
             TypeKind::Array { element_type, size } => {
@@ -312,6 +354,7 @@ impl TypeKind {
             TypeKind::Bool => 1,
             TypeKind::Void => 0,
             TypeKind::Reference { size, .. } => *size,
+            TypeKind::RValueReference { size, .. } => *size,
             TypeKind::Pointer { size, .. } => *size,
             TypeKind::MemberPointer { size, .. } => *size,
             TypeKind::Array { element_type, .. } => element_type.alignment(types),
@@ -343,6 +386,30 @@ impl TypeKind {
         }
     }
 
+    /// Attributes applied to the declaration of a struct, class, union, enum or typedef
+    pub fn attributes(&self) -> &[Attribute] {
+        match self {
+            TypeKind::Struct(struct_decl) => struct_decl.attributes(),
+            TypeKind::Class(class_decl) => class_decl.attributes(),
+            TypeKind::Union(union_decl) => union_decl.attributes(),
+            TypeKind::Enum(enum_decl) => enum_decl.attributes(),
+            TypeKind::Typedef(typedef) => typedef.attributes(),
+            _ => &[],
+        }
+    }
+
+    /// Documentation comment of a struct, class, union, enum or typedef
+    pub fn comment(&self) -> Option<&DocComment> {
+        match self {
+            TypeKind::Struct(struct_decl) => struct_decl.comment(),
+            TypeKind::Class(class_decl) => class_decl.comment(),
+            TypeKind::Union(union_decl) => union_decl.comment(),
+            TypeKind::Enum(enum_decl) => enum_decl.comment(),
+            TypeKind::Typedef(typedef) => typedef.comment(),
+            _ => None,
+        }
+    }
+
     pub fn expand_named<'a>(&'a self, types: &'a Types) -> Option<&'a TypeKind> {
         match self {
             TypeKind::Named(name) => types.get(name),
@@ -354,10 +421,22 @@ impl TypeKind {
         match self {
             TypeKind::Struct(struct_decl) => struct_decl.is_forward_decl(),
             TypeKind::Class(class_decl) => class_decl.is_forward_decl(),
+            TypeKind::Union(union_decl) => union_decl.is_forward_decl(),
             _ => false,
         }
     }
 
+    /// Returns the struct, class or union this type refers to, to access their fields alike
+    pub fn as_record<'a>(&'a self, types: &'a Types) -> Option<&'a dyn RecordFields> {
+        match self {
+            TypeKind::Struct(struct_decl) => Some(struct_decl),
+            TypeKind::Class(class_decl) => Some(class_decl),
+            TypeKind::Union(union_decl) => Some(union_decl),
+            TypeKind::Named(name) => types.get(name)?.as_record(types),
+            _ => None,
+        }
+    }
+
     pub fn as_struct<'a>(&'a self, types: &'a Types) -> Option<&'a StructDecl> {
         match self {
             TypeKind::Struct(struct_decl) => Some(struct_decl),
@@ -392,6 +471,9 @@ impl Display for TypeKind {
             TypeKind::Reference { referenced_type, .. } => {
                 write!(f, "{}&", referenced_type)
             }
+            TypeKind::RValueReference { referenced_type, .. } => {
+                write!(f, "{}&&", referenced_type)
+            }
             TypeKind::Pointer { pointee_type, .. } => {
                 write!(f, "{}*", pointee_type)
             }
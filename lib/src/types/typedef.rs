@@ -1,6 +1,10 @@
 use std::fmt::Display;
 
-use crate::{Env, Types, error::ParseError, types::TypeKind};
+use crate::{
+    Attribute, DocComment, Env, Types,
+    error::{InvalidAstSnafu, ParseError},
+    types::TypeKind,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Typedef {
@@ -8,6 +12,8 @@ pub struct Typedef {
     underlying_type: TypeKind,
     constant: bool,
     volatile: bool,
+    attributes: Vec<Attribute>,
+    comment: Option<DocComment>,
 }
 
 impl Typedef {
@@ -22,6 +28,36 @@ impl Typedef {
             underlying_type: TypeKind::new(env, types, underlying_type)?,
             constant: underlying_type.is_const_qualified(),
             volatile: underlying_type.is_volatile_qualified(),
+            attributes: Vec::new(),
+            comment: None,
+        })
+    }
+
+    /// Creates a typedef from its `TypedefDecl` or `TypeAliasDecl`, with the attributes and doc
+    /// comment of the declaration
+    pub(crate) fn from_node(
+        env: &Env,
+        types: &Types,
+        node: &clang::Entity,
+    ) -> Result<Self, ParseError> {
+        let kind = node.get_kind();
+        if kind != clang::EntityKind::TypedefDecl && kind != clang::EntityKind::TypeAliasDecl {
+            return InvalidAstSnafu { message: format!("Expected TypedefDecl, found: {node:?}") }
+                .fail();
+        }
+
+        let underlying_type = node.get_typedef_underlying_type().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("{kind:?} without underlying type: {node:?}") }
+                .build()
+        })?;
+        let name = node.get_name().ok_or_else(|| {
+            InvalidAstSnafu { message: format!("{kind:?} without name: {node:?}") }.build()
+        })?;
+
+        Ok(Typedef {
+            attributes: Attribute::parse_all(node),
+            comment: DocComment::of(node),
+            ..Self::new(env, types, name, underlying_type)?
         })
     }
 
@@ -40,6 +76,15 @@ impl Typedef {
     pub fn volatile(&self) -> bool {
         self.volatile
     }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
 }
 
 impl Display for Typedef {
@@ -1,19 +1,24 @@
 use std::fmt::Display;
 
 use crate::{
-    Env, Field, Types,
+    Attribute, AttributeKind, Completeness, DocComment, Env, RecordFields, StructField, Types,
     error::{
         AlignofSnafu, InvalidAstSnafu, InvalidFieldsSnafu, ParseError, SizeofSnafu,
         UnsupportedEntitySnafu, UnsupportedTypeSnafu,
     },
+    types::record::find_anonymous_field,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UnionDecl {
     pub(crate) name: Option<String>,
-    fields: Vec<Field>,
+    fields: Vec<StructField>,
     size: usize,
     alignment: usize,
+    attributes: Vec<Attribute>,
+    pack: Option<usize>,
+    completeness: Completeness,
+    comment: Option<DocComment>,
 }
 
 impl UnionDecl {
@@ -58,11 +63,18 @@ impl UnionDecl {
             }
         }
 
+        let Some(node) = ty.get_declaration() else {
+            return InvalidAstSnafu { message: format!("Record type without declaration: {ty:?}") }
+                .fail();
+        };
+        let attributes = Attribute::parse_all(&node);
+        let is_packed = attributes.iter().any(|a| a.kind() == &AttributeKind::Packed);
+
         let mut fields = Vec::new();
         for field in &record_fields {
             match field.get_kind() {
                 clang::EntityKind::FieldDecl => {
-                    fields.push(Field::new(env, types, field)?);
+                    fields.push(StructField::new(env, types, display_name, field, is_packed)?);
                 }
                 _ => {
                     return UnsupportedEntitySnafu {
@@ -77,22 +89,35 @@ impl UnionDecl {
             }
         }
 
+        let completeness = Completeness::of(&node, !fields.is_empty());
+
         let size = ty.get_sizeof().or_else(|e| {
-            if record_fields.is_empty() {
+            if completeness == Completeness::ForwardDeclared {
                 Ok(1)
             } else {
                 SizeofSnafu { type_name: display_name.to_string(), error: e }.fail()
             }
         })?;
         let alignment = ty.get_alignof().or_else(|e| {
-            if record_fields.is_empty() {
+            if completeness == Completeness::ForwardDeclared {
                 Ok(1)
             } else {
                 AlignofSnafu { type_name: display_name.to_string(), error: e }.fail()
             }
         })?;
 
-        Ok(UnionDecl { name, fields, size, alignment })
+        let pack = StructField::apply_pack(env, &node, alignment, &mut fields)?;
+
+        Ok(UnionDecl {
+            name,
+            fields,
+            size,
+            alignment,
+            attributes,
+            pack,
+            completeness,
+            comment: DocComment::of(&node),
+        })
     }
 
     pub fn size(&self) -> usize {
@@ -107,12 +132,61 @@ impl UnionDecl {
         self.name.as_deref()
     }
 
-    pub fn fields(&self) -> &[Field] {
+    pub fn completeness(&self) -> Completeness {
+        self.completeness
+    }
+
+    /// Whether the union is declared but not defined in the parsed headers
+    pub fn is_forward_decl(&self) -> bool {
+        self.completeness == Completeness::ForwardDeclared
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Documentation comment attached to the declaration
+    pub fn comment(&self) -> Option<&DocComment> {
+        self.comment.as_ref()
+    }
+
+    /// Maximum field alignment in bytes set by `#pragma pack`, `None` if no pragma is in effect
+    pub fn pack(&self) -> Option<usize> {
+        self.pack
+    }
+
+    /// Members declared directly in the union, each at offset 0. Members of anonymous structs
+    /// and unions are not listed, use [`Self::get_field`] to find them with their offsets.
+    pub fn fields(&self) -> &[StructField] {
         &self.fields
     }
 
-    pub fn get_field(&self, name: &str) -> Option<&Field> {
-        self.fields.iter().find(|f| f.name() == Some(name))
+    /// Looks up a field in this union or its anonymous struct/union members, with its offset
+    /// within this union
+    pub fn get_field(&self, types: &Types, name: &str) -> Option<StructField> {
+        self.find_field(types, name, 0)
+    }
+
+    pub(crate) fn find_field(
+        &self,
+        types: &Types,
+        name: &str,
+        offset: usize,
+    ) -> Option<StructField> {
+        if let Some(field) = self.fields.iter().find(|f| f.name() == Some(name)) {
+            return Some(field.shifted(offset));
+        }
+        find_anonymous_field(&self.fields, types, name, offset)
+    }
+}
+
+impl RecordFields for UnionDecl {
+    fn fields(&self) -> &[StructField] {
+        self.fields()
+    }
+
+    fn get_field(&self, types: &Types, name: &str) -> Option<StructField> {
+        self.get_field(types, name)
     }
 }
 
@@ -0,0 +1,639 @@
+use std::fmt::Display;
+
+use crate::{
+    Env, TypeKind, Types,
+    error::{InvalidAstSnafu, ParseError},
+    types::layout::{LayoutCache, RecordLayout, is_dynamic, same_record},
+};
+
+/// Virtual table group of a dynamic class, following the Itanium C++ ABI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VTable {
+    virtual_base_offsets: Vec<VirtualBaseOffset>,
+    entries: Vec<VTableEntry>,
+    secondary: Vec<SecondaryVTable>,
+}
+
+/// Virtual table of a non-primary base class subobject
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecondaryVTable {
+    base_name: String,
+    /// Offset in bytes of the base class subobject
+    offset: usize,
+    is_virtual: bool,
+    virtual_base_offsets: Vec<VirtualBaseOffset>,
+    entries: Vec<VTableEntry>,
+}
+
+/// Offset of a virtual base class subobject, stored in a vtable so that the virtual base can be
+/// found at runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VirtualBaseOffset {
+    base_name: String,
+    /// Offset in bytes from the subobject which the vtable belongs to
+    offset: isize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VTableEntry {
+    index: usize,
+    /// Class which declares the final overrider
+    class_name: String,
+    name: String,
+    kind: VTableEntryKind,
+    signature: TypeKind,
+    is_pure_virtual: bool,
+    thunk: Option<Thunk>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VTableEntryKind {
+    Function,
+    CompleteDestructor,
+    DeletingDestructor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thunk {
+    /// Adjustment in bytes applied to `this` before calling the final overrider
+    this_adjustment: isize,
+    /// Whether the adjustment goes through a virtual base, and must be loaded from the vtable
+    is_virtual: bool,
+    /// Conversion of the pointer returned by a covariant final overrider to the return type of
+    /// the function which the slot was introduced for
+    return_adjustment: Option<ReturnAdjustment>,
+}
+
+/// Conversion of a returned pointer or reference to one of a base class of the returned class
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReturnAdjustment {
+    /// Offset in bytes of the base class, from the virtual base on the way to it if any
+    offset: isize,
+    /// Whether the base class is in a virtual base, whose offset must be loaded from the vtable of
+    /// the returned object
+    is_virtual: bool,
+}
+
+#[derive(Clone)]
+struct Slot<'tu> {
+    method: clang::Entity<'tu>,
+    /// Virtual function which introduced the slot, whose return type callers expect
+    declared: clang::Entity<'tu>,
+    class: clang::Entity<'tu>,
+    kind: VTableEntryKind,
+    thunk: Option<Thunk>,
+}
+
+struct Table<'tu> {
+    base: clang::Entity<'tu>,
+    offset: usize,
+    is_virtual: bool,
+    slots: Vec<Slot<'tu>>,
+}
+
+struct Group<'tu> {
+    primary: Vec<Slot<'tu>>,
+    secondary: Vec<Table<'tu>>,
+}
+
+impl VTable {
+    pub(crate) fn new<'tu>(
+        env: &Env,
+        types: &Types,
+        layouts: &mut LayoutCache<'tu>,
+        decl: &clang::Entity<'tu>,
+    ) -> Result<Option<Self>, ParseError> {
+        if !is_dynamic(decl)? {
+            return Ok(None);
+        }
+        let layout = layouts.get(env, decl)?;
+
+        let Group { primary, mut secondary } = Self::build_group(env, layouts, decl)?;
+
+        if !layout.virtual_bases.is_empty() {
+            let mut subobjects = Vec::new();
+            Self::collect_subobjects(env, layouts, decl, 0, &layout, &mut subobjects)?;
+
+            for virtual_base in &layout.virtual_bases {
+                // Primary virtual bases are part of the vtables of the classes sharing them
+                if virtual_base.is_primary || !is_dynamic(&virtual_base.decl)? {
+                    continue;
+                }
+                let group = Self::build_group(env, layouts, &virtual_base.decl)?;
+                let mut tables = vec![Table {
+                    base: virtual_base.decl,
+                    offset: virtual_base.offset,
+                    is_virtual: true,
+                    slots: group.primary,
+                }];
+                tables.extend(group.secondary.into_iter().map(|table| Table {
+                    offset: table.offset + virtual_base.offset,
+                    is_virtual: true,
+                    ..table
+                }));
+                for table in &mut tables {
+                    for (class, offset) in &subobjects {
+                        Self::apply_overrides(
+                            env,
+                            layouts,
+                            &mut table.slots,
+                            class,
+                            *offset,
+                            table.offset,
+                            true,
+                        )?;
+                    }
+                }
+                secondary.extend(tables);
+            }
+        }
+
+        let virtual_base_offsets =
+            Self::collect_virtual_base_offsets(env, layouts, decl, 0, &layout)?;
+        let entries = Self::to_entries(env, types, &primary)?;
+        let secondary = secondary
+            .iter()
+            .map(|table| {
+                Ok(SecondaryVTable {
+                    base_name: record_name(&table.base),
+                    offset: table.offset,
+                    is_virtual: table.is_virtual,
+                    virtual_base_offsets: Self::collect_virtual_base_offsets(
+                        env,
+                        layouts,
+                        &table.base,
+                        table.offset,
+                        &layout,
+                    )?,
+                    entries: Self::to_entries(env, types, &table.slots)?,
+                })
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        Ok(Some(VTable { virtual_base_offsets, entries, secondary }))
+    }
+
+    /// Builds the vtables of a class and its non-virtual bases
+    fn build_group<'tu>(
+        env: &Env,
+        layouts: &mut LayoutCache<'tu>,
+        decl: &clang::Entity<'tu>,
+    ) -> Result<Group<'tu>, ParseError> {
+        let layout = layouts.get(env, decl)?;
+
+        let mut primary = Vec::new();
+        let mut secondary = Vec::new();
+        if let Some(base) = &layout.primary_virtual_base {
+            // A nearly empty virtual base shares the primary vtable with the class
+            let group = Self::build_group(env, layouts, base)?;
+            primary = group.primary;
+            secondary.extend(
+                group.secondary.into_iter().map(|table| Table { is_virtual: true, ..table }),
+            );
+        }
+        for base in &layout.bases {
+            if base.is_virtual || !is_dynamic(&base.decl)? {
+                continue;
+            }
+            let group = Self::build_group(env, layouts, &base.decl)?;
+            if base.is_primary {
+                primary = group.primary;
+            } else {
+                secondary.push(Table {
+                    base: base.decl,
+                    offset: base.offset,
+                    is_virtual: false,
+                    slots: group.primary,
+                });
+            }
+            secondary.extend(
+                group
+                    .secondary
+                    .into_iter()
+                    .map(|table| Table { offset: table.offset + base.offset, ..table }),
+            );
+        }
+
+        Self::apply_overrides(env, layouts, &mut primary, decl, 0, 0, false)?;
+        for table in &mut secondary {
+            Self::apply_overrides(env, layouts, &mut table.slots, decl, 0, table.offset, false)?;
+        }
+
+        // Virtual functions which don't override a function in the primary base, or whose
+        // covariant return type must be adjusted for it, get new slots
+        for method in virtual_methods(decl) {
+            let is_overrider = |slot: &Slot| {
+                same_entity(&slot.method, &method)
+                    && slot.thunk.is_none_or(|thunk| thunk.return_adjustment.is_none())
+            };
+            if primary.iter().any(is_overrider) {
+                continue;
+            }
+            if method.get_kind() == clang::EntityKind::Destructor {
+                for kind in
+                    [VTableEntryKind::CompleteDestructor, VTableEntryKind::DeletingDestructor]
+                {
+                    primary.push(Slot {
+                        method,
+                        declared: method,
+                        class: *decl,
+                        kind,
+                        thunk: None,
+                    });
+                }
+            } else {
+                primary.push(Slot {
+                    method,
+                    declared: method,
+                    class: *decl,
+                    kind: VTableEntryKind::Function,
+                    thunk: None,
+                });
+            }
+        }
+
+        Ok(Group { primary, secondary })
+    }
+
+    /// Lists every class subobject in the hierarchy with its offset, bases before derived classes
+    fn collect_subobjects<'tu>(
+        env: &Env,
+        layouts: &mut LayoutCache<'tu>,
+        decl: &clang::Entity<'tu>,
+        offset: usize,
+        complete_layout: &RecordLayout<'tu>,
+        subobjects: &mut Vec<(clang::Entity<'tu>, usize)>,
+    ) -> Result<(), ParseError> {
+        let layout = layouts.get(env, decl)?;
+        for base in &layout.bases {
+            let base_offset = if base.is_virtual {
+                let Some(virtual_base) =
+                    complete_layout.virtual_bases.iter().find(|v| same_record(&v.decl, &base.decl))
+                else {
+                    continue;
+                };
+                virtual_base.offset
+            } else {
+                offset + base.offset
+            };
+            Self::collect_subobjects(
+                env,
+                layouts,
+                &base.decl,
+                base_offset,
+                complete_layout,
+                subobjects,
+            )?;
+        }
+        subobjects.push((*decl, offset));
+        Ok(())
+    }
+
+    /// Lists the virtual bases of `decl` in inheritance graph order, with their offsets from a
+    /// subobject of `decl` at `offset` within the complete object
+    fn collect_virtual_base_offsets<'tu>(
+        env: &Env,
+        layouts: &mut LayoutCache<'tu>,
+        decl: &clang::Entity<'tu>,
+        offset: usize,
+        complete_layout: &RecordLayout<'tu>,
+    ) -> Result<Vec<VirtualBaseOffset>, ParseError> {
+        let layout = layouts.get(env, decl)?;
+        Ok(layout
+            .virtual_bases
+            .iter()
+            .filter_map(|base| {
+                let virtual_base = complete_layout
+                    .virtual_bases
+                    .iter()
+                    .find(|v| same_record(&v.decl, &base.decl))?;
+                Some(VirtualBaseOffset {
+                    base_name: record_name(&base.decl),
+                    offset: virtual_base.offset as isize - offset as isize,
+                })
+            })
+            .collect())
+    }
+
+    /// Replaces slots which are overridden by a virtual function in `class`
+    fn apply_overrides<'tu>(
+        env: &Env,
+        layouts: &mut LayoutCache<'tu>,
+        slots: &mut [Slot<'tu>],
+        class: &clang::Entity<'tu>,
+        class_offset: usize,
+        table_offset: usize,
+        is_virtual: bool,
+    ) -> Result<(), ParseError> {
+        let this_adjustment = class_offset as isize - table_offset as isize;
+        let thunk = |return_adjustment: Option<ReturnAdjustment>| {
+            (this_adjustment != 0 || return_adjustment.is_some()).then_some(Thunk {
+                this_adjustment,
+                is_virtual: is_virtual && this_adjustment != 0,
+                return_adjustment,
+            })
+        };
+        let methods = virtual_methods(class);
+        for method in &methods {
+            let overridden = method.get_overridden_methods().unwrap_or_default();
+            for slot in slots.iter_mut() {
+                if overridden.iter().any(|o| same_entity(o, &slot.method)) {
+                    let return_adjustment =
+                        return_adjustment(env, layouts, method, &slot.declared)?;
+                    slot.method = *method;
+                    slot.class = *class;
+                    slot.thunk = thunk(return_adjustment);
+                }
+            }
+        }
+
+        // Every class has its own destructor, whether declared or implicit
+        let destructor = methods.iter().find(|m| m.get_kind() == clang::EntityKind::Destructor);
+        for slot in slots.iter_mut().filter(|s| s.kind != VTableEntryKind::Function) {
+            if let Some(destructor) = destructor {
+                slot.method = *destructor;
+            }
+            slot.class = *class;
+            slot.thunk = thunk(None);
+        }
+        Ok(())
+    }
+
+    fn to_entries(
+        env: &Env,
+        types: &Types,
+        slots: &[Slot],
+    ) -> Result<Vec<VTableEntry>, ParseError> {
+        slots
+            .iter()
+            .enumerate()
+            .map(|(index, slot)| {
+                let name = if slot.kind == VTableEntryKind::Function {
+                    slot.method.get_name()
+                } else {
+                    slot.class.get_name().map(|name| format!("~{name}"))
+                }
+                .ok_or_else(|| {
+                    InvalidAstSnafu { message: format!("Method without name: {:?}", slot.method) }
+                        .build()
+                })?;
+                let ty = slot.method.get_type().ok_or_else(|| {
+                    InvalidAstSnafu { message: format!("Method without type: {:?}", slot.method) }
+                        .build()
+                })?;
+                Ok(VTableEntry {
+                    index,
+                    class_name: record_name(&slot.class),
+                    name,
+                    kind: slot.kind,
+                    signature: TypeKind::new(env, types, ty)?,
+                    is_pure_virtual: slot.method.is_pure_virtual_method(),
+                    thunk: slot.thunk,
+                })
+            })
+            .collect()
+    }
+
+    /// Offsets of the virtual bases from the start of the class
+    pub fn virtual_base_offsets(&self) -> &[VirtualBaseOffset] {
+        &self.virtual_base_offsets
+    }
+
+    /// Virtual function entries of the primary vtable
+    pub fn entries(&self) -> &[VTableEntry] {
+        &self.entries
+    }
+
+    /// Vtables of base class subobjects which don't share the primary vtable
+    pub fn secondary(&self) -> &[SecondaryVTable] {
+        &self.secondary
+    }
+
+    pub fn get_entry(&self, name: &str) -> Option<&VTableEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+}
+
+fn virtual_methods<'tu>(decl: &clang::Entity<'tu>) -> Vec<clang::Entity<'tu>> {
+    decl.get_children()
+        .into_iter()
+        .filter(|c| {
+            matches!(c.get_kind(), clang::EntityKind::Method | clang::EntityKind::Destructor)
+                && c.is_virtual_method()
+        })
+        .collect()
+}
+
+/// Returns how the pointer or reference returned by a covariant overrider is converted to the
+/// return type of the overridden function, `None` if it's returned as is
+fn return_adjustment<'tu>(
+    env: &Env,
+    layouts: &mut LayoutCache<'tu>,
+    overrider: &clang::Entity<'tu>,
+    overridden: &clang::Entity<'tu>,
+) -> Result<Option<ReturnAdjustment>, ParseError> {
+    let (Some(derived), Some(base)) = (returned_record(overrider), returned_record(overridden))
+    else {
+        return Ok(None);
+    };
+    let Some((offset, is_virtual)) = base_offset(env, layouts, &derived, &base)? else {
+        return InvalidAstSnafu {
+            message: format!("Covariant return type without its base: {overrider:?}"),
+        }
+        .fail();
+    };
+    Ok((offset != 0 || is_virtual).then_some(ReturnAdjustment { offset, is_virtual }))
+}
+
+/// Returns the class which a method returns a pointer or reference to
+fn returned_record<'tu>(method: &clang::Entity<'tu>) -> Option<clang::Entity<'tu>> {
+    let ty = method.get_result_type()?.get_canonical_type();
+    if !matches!(
+        ty.get_kind(),
+        clang::TypeKind::Pointer
+            | clang::TypeKind::LValueReference
+            | clang::TypeKind::RValueReference
+    ) {
+        return None;
+    }
+    let decl = ty.get_pointee_type()?.get_canonical_type().get_declaration()?;
+    Some(decl.get_definition().unwrap_or(decl))
+}
+
+/// Returns the offset of `base` within `decl`, from the last virtual base on the way to it if
+/// any, and whether there is such a virtual base
+fn base_offset<'tu>(
+    env: &Env,
+    layouts: &mut LayoutCache<'tu>,
+    decl: &clang::Entity<'tu>,
+    base: &clang::Entity<'tu>,
+) -> Result<Option<(isize, bool)>, ParseError> {
+    if same_record(decl, base) {
+        return Ok(Some((0, false)));
+    }
+    let layout = layouts.get(env, decl)?;
+    for direct in &layout.bases {
+        let Some((offset, is_virtual)) = base_offset(env, layouts, &direct.decl, base)? else {
+            continue;
+        };
+        return Ok(Some(if is_virtual || direct.is_virtual {
+            (offset, true)
+        } else {
+            (direct.offset as isize + offset, false)
+        }));
+    }
+    Ok(None)
+}
+
+fn same_entity(a: &clang::Entity, b: &clang::Entity) -> bool {
+    a.get_canonical_entity() == b.get_canonical_entity()
+}
+
+fn record_name(decl: &clang::Entity) -> String {
+    decl.get_type().map(|ty| ty.get_display_name()).unwrap_or_default()
+}
+
+impl SecondaryVTable {
+    pub fn base_name(&self) -> &str {
+        &self.base_name
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
+    /// Offsets of the base class's virtual bases from the base class subobject
+    pub fn virtual_base_offsets(&self) -> &[VirtualBaseOffset] {
+        &self.virtual_base_offsets
+    }
+
+    pub fn entries(&self) -> &[VTableEntry] {
+        &self.entries
+    }
+}
+
+impl VirtualBaseOffset {
+    pub fn base_name(&self) -> &str {
+        &self.base_name
+    }
+
+    pub fn offset(&self) -> isize {
+        self.offset
+    }
+}
+
+impl VTableEntry {
+    /// Index of the entry among the virtual function pointers of its vtable
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> VTableEntryKind {
+        self.kind
+    }
+
+    pub fn signature(&self) -> &TypeKind {
+        &self.signature
+    }
+
+    pub fn is_pure_virtual(&self) -> bool {
+        self.is_pure_virtual
+    }
+
+    pub fn thunk(&self) -> Option<&Thunk> {
+        self.thunk.as_ref()
+    }
+}
+
+impl Thunk {
+    pub fn this_adjustment(&self) -> isize {
+        self.this_adjustment
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
+    pub fn return_adjustment(&self) -> Option<&ReturnAdjustment> {
+        self.return_adjustment.as_ref()
+    }
+}
+
+impl ReturnAdjustment {
+    pub fn offset(&self) -> isize {
+        self.offset
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+}
+
+impl Display for VTableEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}::{}", self.index, self.class_name, self.name)?;
+        match self.kind {
+            VTableEntryKind::Function => {}
+            VTableEntryKind::CompleteDestructor => write!(f, " [complete]")?,
+            VTableEntryKind::DeletingDestructor => write!(f, " [deleting]")?,
+        }
+        if self.is_pure_virtual {
+            write!(f, " = 0")?;
+        }
+        if let Some(thunk) = &self.thunk {
+            if thunk.this_adjustment != 0 {
+                write!(
+                    f,
+                    " [this adjustment: {}{}]",
+                    thunk.this_adjustment,
+                    if thunk.is_virtual { " virtual" } else { "" }
+                )?;
+            }
+            if let Some(adjustment) = &thunk.return_adjustment {
+                write!(
+                    f,
+                    " [return adjustment: {}{}]",
+                    adjustment.offset,
+                    if adjustment.is_virtual { " virtual" } else { "" }
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for VTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "vtable {{")?;
+        for base in &self.virtual_base_offsets {
+            writeln!(f, "  vbase offset {}: {}", base.base_name, base.offset)?;
+        }
+        for entry in &self.entries {
+            writeln!(f, "  {entry}")?;
+        }
+        for table in &self.secondary {
+            writeln!(f, "  {} ({:#x}) {{", table.base_name, table.offset)?;
+            for base in &table.virtual_base_offsets {
+                writeln!(f, "    vbase offset {}: {}", base.base_name, base.offset)?;
+            }
+            for entry in &table.entries {
+                writeln!(f, "    {entry}")?;
+            }
+            writeln!(f, "  }}")?;
+        }
+        write!(f, "}}")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+struct Shape {
+    virtual Shape *clone() const;
+
+    int sides;
+};
+
+struct Tagged {
+    virtual ~Tagged();
+
+    long tag;
+};
+
+struct Square : Tagged, Shape {
+    Square *clone() const override;
+};
+
+struct Circle : Shape {
+    Circle *clone() const override;
+};
+
+struct Canvas : Shape {
+    Square *clone() const override;
+};
+
+struct Sphere : virtual Shape {
+    Sphere *clone() const override;
+};
@@ -0,0 +1,15 @@
+class Members {
+public:
+    int publicValue;
+
+    static const int MAX_COUNT = 16;
+    static constexpr float SCALE = 1.5f;
+    static Members *instance;
+
+protected:
+    int protectedValue;
+
+private:
+    int privateValue;
+    static constexpr unsigned long long MASK = 0xffffffff00000000ull;
+};
@@ -0,0 +1,28 @@
+class Other;
+
+class Base {
+public:
+    virtual ~Base();
+    virtual void update() = 0;
+    virtual int getValue() const;
+};
+
+class Methods : public Base {
+public:
+    Methods();
+    Methods(const Methods &) = delete;
+    Methods(Methods &&other);
+    ~Methods() override;
+
+    void update() override;
+    static Methods *create(int value);
+    bool operator==(const Methods &other) const;
+    operator bool() const;
+    void useOther(Other *other);
+
+protected:
+    void helper();
+
+private:
+    int value;
+};
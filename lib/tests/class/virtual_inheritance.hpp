@@ -0,0 +1,35 @@
+struct A {
+    virtual void f();
+};
+
+struct B : virtual A {
+    void f() override;
+    int b;
+};
+
+struct C : B {
+    virtual void g();
+    int c;
+};
+
+struct L : virtual A {
+    int l;
+};
+
+struct R : virtual A {
+    int r;
+};
+
+struct D : L, R {
+    int d;
+};
+
+struct V {
+    virtual void f();
+    int v;
+};
+
+struct W : virtual V {
+    void f() override;
+    int w;
+};
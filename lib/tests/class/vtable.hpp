@@ -0,0 +1,25 @@
+class Animal {
+public:
+    virtual ~Animal();
+    virtual void speak() = 0;
+    virtual int legs() const;
+
+    int age;
+};
+
+class Named {
+public:
+    virtual const char *name() const;
+    virtual void rename(const char *name);
+
+    int id;
+};
+
+class Dog : public Animal, public Named {
+public:
+    void speak() override;
+    const char *name() const override;
+    virtual void fetch();
+
+    int tricks;
+};
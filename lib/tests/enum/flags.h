@@ -0,0 +1,18 @@
+enum __attribute__((flag_enum)) Permissions {
+    PERM_READ  = 1,
+    PERM_WRITE = 2,
+};
+
+enum Style {
+    STYLE_NONE      = 0,
+    STYLE_BOLD      = 1 << 0,
+    STYLE_ITALIC    = 1 << 1,
+    STYLE_UNDERLINE = 1 << 2,
+    STYLE_ALL       = STYLE_BOLD | STYLE_ITALIC | STYLE_UNDERLINE,
+};
+
+enum Sequential {
+    SEQUENTIAL_A = 1,
+    SEQUENTIAL_B = 2,
+    SEQUENTIAL_C = 3,
+};
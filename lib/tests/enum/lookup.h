@@ -0,0 +1,18 @@
+enum {
+    ITEM_NONE,
+    ITEM_SWORD,
+    ITEM_SHIELD,
+};
+
+typedef enum {
+    ACTOR_PLAYER = 0,
+    ACTOR_HERO = ACTOR_PLAYER,
+    ACTOR_ENEMY = 1,
+} ActorKind;
+
+typedef ActorKind ActorKindAlias;
+
+typedef enum {
+    STATE_FROZEN = 1 << 0,
+    STATE_BURNING = 1 << 2,
+} StateFlags;
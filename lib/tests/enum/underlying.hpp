@@ -0,0 +1,25 @@
+typedef unsigned char u8;
+
+enum Plain {
+    PLAIN_A,
+    PLAIN_B,
+};
+
+enum Negative {
+    NEGATIVE_A = -1,
+    NEGATIVE_B = 1,
+};
+
+enum Fixed : u8 {
+    FIXED_A,
+    FIXED_B,
+};
+
+enum class Scoped {
+    A,
+    B,
+};
+
+enum class ScopedFixed : long long {
+    A,
+};
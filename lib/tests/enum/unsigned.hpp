@@ -0,0 +1,12 @@
+enum Big : unsigned long long {
+    BIG_ZERO = 0,
+    BIG_MAX = 0xFFFFFFFFFFFFFFFF,
+};
+
+enum Word : unsigned int {
+    WORD_MAX = 0xFFFFFFFF,
+};
+
+enum Signed : int {
+    SIGNED_MINUS_ONE = -1,
+};
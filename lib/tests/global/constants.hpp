@@ -0,0 +1,17 @@
+typedef unsigned int u32;
+
+static const int kMaxHp = 100;
+constexpr u32 FLAGS = 1 << 4 | 1;
+const char *const kName = "player";
+const float kGravity = 9.5f;
+int notConstant = 5;
+extern const int kExternal;
+
+namespace game {
+    constexpr long long kBig = 1LL << 40;
+}
+
+enum {
+    ITEM_NONE,
+    ITEM_SWORD,
+};
@@ -0,0 +1,77 @@
+#include "../struct/simple.h"
+
+#define MY_CONSTANT 42
+
+namespace outer {
+    struct InNamespace {
+        int x;
+    };
+
+    inline namespace inner {
+        struct InInlineNamespace {
+            int y;
+        };
+    }
+}
+
+namespace alias = outer;
+using namespace outer;
+using outer::InNamespace;
+
+using AliasedInt = int;
+
+extern "C" {
+    void c_function(int a);
+}
+
+static_assert(sizeof(int) == 4, "int must be 4 bytes");
+
+template <typename T>
+struct Box {
+    T value;
+};
+
+template <typename T>
+struct Box<T *> {
+    T *pointer;
+};
+
+template <>
+struct Box<char> {
+    char c;
+};
+
+extern template struct Box<int>;
+
+template <typename T>
+T identity(T value) {
+    return value;
+}
+
+extern template int identity<int>(int);
+
+template <typename T>
+constexpr T zero = T(0);
+
+template <typename T>
+using BoxAlias = Box<T>;
+
+class WithMethods {
+public:
+    WithMethods();
+    ~WithMethods();
+    operator int() const;
+    void method();
+
+    friend class FriendClass;
+    friend void friend_function();
+};
+
+WithMethods::WithMethods() {}
+WithMethods::~WithMethods() {}
+WithMethods::operator int() const { return 0; }
+void WithMethods::method() {}
+
+int global_variable;
+
+asm(".globl dummy_symbol");
@@ -0,0 +1,19 @@
+struct Actor;
+
+extern "C" {
+    void Actor_Update(Actor *actor, float delta);
+    int Debug_Printf(const char *format, ...);
+}
+
+static int clamp(int value, int min, int max) {
+    return value < min ? min : value > max ? max : value;
+}
+
+__attribute__((ms_abi)) void *Alloc(unsigned long size);
+void Unnamed(int, char);
+__attribute__((section(".init"))) void Init();
+
+namespace game {
+    void Reset();
+    void Reset(int level);
+}
@@ -0,0 +1,3 @@
+#include "macro_base.h"
+
+#define DERIVED (BASE_COUNT + 1)
@@ -0,0 +1 @@
+#define BASE_COUNT 4
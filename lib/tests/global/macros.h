@@ -0,0 +1,27 @@
+#ifndef MACROS_H
+#define MACROS_H
+
+#define MAX_ACTORS 64
+#define HALF_ACTORS (MAX_ACTORS / 2)
+#define MASK 0xFFFFFFFFu
+#define GRAVITY 9.5f
+#define PLAYER_NAME "player"
+#define NEGATIVE -5
+#define SUM 1 + 2
+#define PRODUCT SUM * 3
+#define BYTE ((unsigned char)0x1FF)
+#define LETTER 'A'
+#define GREETING "hello, " PLAYER_NAME
+#define FORWARD (BACKWARD * 2)
+#define BACKWARD 3
+#define VERSION 1
+#undef VERSION
+#define VERSION 2
+#define SELF (SELF + 1)
+#define WRAPPED (0xFFFFFFFFFFFFFFFFULL * 0xFFFFFFFFFFFFFFFFULL)
+
+#define SQUARE(x) ((x) * (x))
+#define u32 unsigned int
+#define UNUSED __attribute__((unused))
+
+#endif
@@ -0,0 +1,3 @@
+void SetCallback(void (*callback)());
+int NoPrototype();
+extern _Complex double gComplex;
@@ -0,0 +1,8 @@
+constexpr auto kAuto = 1;
+extern decltype(nullptr) gNull;
+extern int gVector __attribute__((vector_size(16)));
+extern __int128 gWide;
+void TakeWide(__int128 value);
+
+int gKept;
+void Kept();
@@ -0,0 +1,17 @@
+typedef unsigned char u8;
+
+struct Actor {
+    int hp;
+};
+
+extern Actor *gPlayer;
+extern u8 gSaveData[0x1000];
+static int sFrameCount;
+int gScore = 0;
+extern int gLives;
+int gLives = 3;
+__attribute__((section(".sbss"))) int gDebugFlags;
+
+namespace game {
+    extern Actor *gActors[];
+}
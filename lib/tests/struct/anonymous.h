@@ -0,0 +1,28 @@
+typedef struct {
+    unsigned int control;
+    union {
+        unsigned int raw;
+        struct {
+            unsigned short low;
+            unsigned short high;
+        };
+    };
+    struct {
+        unsigned char mode;
+        union {
+            unsigned char flags;
+            unsigned char bits;
+        };
+    };
+    struct {
+        int x;
+    } named;
+} Register;
+
+typedef union {
+    unsigned long long value;
+    struct {
+        unsigned int lo;
+        unsigned int hi;
+    };
+} Word;
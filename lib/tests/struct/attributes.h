@@ -0,0 +1,23 @@
+typedef struct Actor Actor;
+
+struct __attribute__((aligned(8))) Node {
+    Actor *owner __attribute__((annotate("ptr:Actor")));
+    int legacyFlags __attribute__((deprecated("use flags instead")));
+    int flags;
+};
+
+struct __attribute__((packed)) PackedHeader {
+    char tag;
+    int length;
+};
+
+struct Config {
+    int value;
+} __attribute__((section(".data")));
+
+typedef unsigned int ObjectId __attribute__((annotate("id")));
+
+enum State {
+    STATE_IDLE,
+    STATE_OLD __attribute__((deprecated)),
+};
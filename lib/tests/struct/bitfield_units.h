@@ -0,0 +1,6 @@
+typedef struct {
+    unsigned char kind;
+    unsigned short a : 4;
+    unsigned short b : 6;
+    unsigned int c : 3;
+} Flags;
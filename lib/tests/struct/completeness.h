@@ -0,0 +1,7 @@
+struct Opaque;
+
+struct Tag {};
+
+struct Full {
+    int x;
+};
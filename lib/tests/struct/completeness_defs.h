@@ -0,0 +1,5 @@
+struct Opaque {
+    int x;
+};
+
+struct Tag;
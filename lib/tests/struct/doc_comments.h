@@ -0,0 +1,22 @@
+/**
+ * Player state saved to the memory card.
+ *
+ * Written once per frame.
+ */
+struct SaveData {
+    /// Current health points
+    int hp;
+    int mp; ///< Current magic points
+    // Not a doc comment
+    int level;
+};
+
+/** \brief Actor categories */
+enum ActorKind {
+    /// Controlled by the player
+    ACTOR_PLAYER,
+    ACTOR_ENEMY, /**< Hostile */
+};
+
+/// Frame counter
+typedef unsigned int FrameCount;
@@ -0,0 +1,27 @@
+struct A {
+    int a;
+};
+
+struct B {
+    double b;
+};
+
+struct C : A, private B {
+    int c;
+};
+
+struct V {
+    int v;
+};
+
+struct L : virtual V {
+    int l;
+};
+
+struct R : virtual V {
+    int r;
+};
+
+struct D : L, R {
+    int d;
+};
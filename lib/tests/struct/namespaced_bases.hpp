@@ -0,0 +1,13 @@
+namespace ns {
+struct V {
+    int v;
+};
+
+struct X : virtual V {
+    int x;
+};
+} // namespace ns
+
+struct Y : ns::X {
+    int y;
+};
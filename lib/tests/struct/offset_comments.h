@@ -0,0 +1,25 @@
+struct Actor {
+    /* 0x00 */ int id;
+    /* 0x04 */ short x, y;
+    /* 0x0C */ char flags;
+    /* 0x0C */ union {
+        /* 0x0C */ int hp;
+        /* 0x0D */ float energy;
+    };
+    int uncommented;
+    // 0x20
+    long long timer;
+}; /* size 0x28 */
+
+typedef struct Item {
+    /* 0x0 */ int kind;
+    /* 0x4 */ int count;
+} Item; // size = 0x10
+
+typedef struct {
+    /* 0x00 */ int a;
+    /* 0x02 */ int b;
+} Anonymous;
+
+// Declarations the crawler does not support do not prevent checking records
+typedef int Vector __attribute__((vector_size(16)));
@@ -0,0 +1,17 @@
+struct Player {
+    int id;
+    /* 0x08 */ short x;
+    short y; // y position
+    /* 0x08 */ char flags;
+    union {
+        int hp;
+        float energy;
+    };
+}; // player state
+
+typedef struct Pair {
+    int a;
+    /* 0x8 */ long long b;
+    // 0x20
+    int c;
+} Pair;
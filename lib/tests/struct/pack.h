@@ -0,0 +1,75 @@
+#pragma pack(push, 1)
+struct Packed1 {
+    char tag;
+    int length;
+    short flags;
+};
+#pragma pack(pop)
+
+#pragma pack(push, 2)
+struct Packed2 {
+    char tag;
+    int length;
+    char pad;
+};
+#pragma pack(pop)
+
+#pragma pack(push, 8)
+struct NoEffect {
+    char tag;
+    int length;
+};
+#pragma pack(pop)
+
+#pragma pack(push, 2)
+struct NoEffectTight {
+    char tag;
+    char kind;
+    short length;
+};
+#pragma pack(pop)
+
+#pragma pack(push, 4)
+struct __attribute__((aligned(16))) Packed4Aligned {
+    double value;
+    int length;
+};
+#pragma pack(pop)
+
+#pragma pack(push, 2)
+#pragma pack(push, 1)
+#pragma pack(pop)
+struct RestoredPack {
+    char tag;
+    int length;
+};
+#pragma pack(pop)
+
+#pragma pack(1)
+#pragma pack()
+struct ResetPack {
+    char tag;
+    int length;
+};
+
+struct Unpacked {
+    char tag;
+    int length;
+};
+
+struct __attribute__((packed)) AttributePacked {
+    char tag;
+    int length;
+};
+
+#pragma pack(push, 1)
+struct __attribute__((aligned(8))) PackedAligned {
+    char tag;
+    int length;
+};
+
+union PackedUnion {
+    char bytes[3];
+    int value;
+};
+#pragma pack(pop)
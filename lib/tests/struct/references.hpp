@@ -0,0 +1,9 @@
+class Incomplete;
+
+struct References {
+    char &ch;
+    const double &value;
+    int &&moved;
+
+    void take(Incomplete &incomplete);
+};
@@ -0,0 +1,50 @@
+struct Empty {};
+
+struct Plain {
+    int a;
+    char b;
+};
+
+struct __attribute__((packed)) Packed {
+    char a;
+    int b;
+};
+
+struct alignas(16) Aligned {
+    int a;
+};
+
+struct MixedAccess {
+    int a;
+
+private:
+    int b;
+};
+
+struct Copyable {
+    Copyable(const Copyable &) = default;
+    int a;
+};
+
+struct NotCopyable {
+    NotCopyable(const NotCopyable &other);
+    int a;
+};
+
+struct Abstract {
+    virtual void f() = 0;
+};
+
+struct Concrete : Abstract {
+    void f() override;
+};
+
+struct IntAssignable {
+    IntAssignable &operator=(int value);
+    int a;
+};
+
+struct CopyAssignable {
+    CopyAssignable &operator=(const CopyAssignable &other);
+    int a;
+};
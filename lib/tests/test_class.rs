@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use type_crawler::{Env, EnvOptions, TypeCrawler, TypeKind};
+    use type_crawler::{
+        Access, ConstantValue, Env, EnvOptions, MethodKind, TypeCrawler, TypeKind, VTableEntryKind,
+    };
 
     #[test]
     fn test_virtual() {
@@ -21,4 +23,298 @@ mod tests {
         assert_eq!(virtual_class.fields()[0].offset_bytes(), 8);
         assert_eq!(virtual_class.fields()[0].kind(), &TypeKind::S32);
     }
+
+    #[test]
+    fn test_methods() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/class/methods.hpp").unwrap();
+
+        let TypeKind::Class(base) = types.get("Base").unwrap() else {
+            panic!("Expected Class type");
+        };
+        assert_eq!(base.methods().len(), 3);
+        let update = base.get_method("update").unwrap();
+        assert!(update.is_virtual());
+        assert!(update.is_pure_virtual());
+        assert!(!update.is_override());
+        let get_value = base.get_method("getValue").unwrap();
+        assert!(get_value.is_virtual());
+        assert!(get_value.is_const());
+        assert!(!get_value.is_pure_virtual());
+        let TypeKind::Function { return_type, parameters } = get_value.signature() else {
+            panic!("Expected Function type, found: {:?}", get_value.signature());
+        };
+        assert_eq!(**return_type, TypeKind::S32);
+        assert!(parameters.is_empty());
+
+        let TypeKind::Class(methods) = types.get("Methods").unwrap() else {
+            panic!("Expected Class type");
+        };
+        assert_eq!(methods.methods().len(), 10);
+
+        let constructors = methods
+            .methods()
+            .iter()
+            .filter(|m| m.kind() == MethodKind::Constructor)
+            .collect::<Vec<_>>();
+        assert_eq!(constructors.len(), 3);
+        assert!(!constructors[0].is_deleted());
+        assert!(constructors[1].is_deleted());
+        assert!(!constructors[2].is_deleted());
+        let TypeKind::Function { parameters, .. } = constructors[2].signature() else {
+            panic!("Expected Function type, found: {:?}", constructors[2].signature());
+        };
+        assert!(matches!(parameters[0], TypeKind::RValueReference { .. }));
+
+        let destructor = methods.get_method("~Methods").unwrap();
+        assert_eq!(destructor.kind(), MethodKind::Destructor);
+        assert!(destructor.is_virtual());
+        assert!(destructor.is_override());
+
+        let update = methods.get_method("update").unwrap();
+        assert!(update.is_virtual());
+        assert!(update.is_override());
+        assert!(!update.is_pure_virtual());
+
+        let create = methods.get_method("create").unwrap();
+        assert!(create.is_static());
+        assert!(!create.is_virtual());
+
+        let equals = methods.get_method("operator==").unwrap();
+        assert_eq!(equals.kind(), MethodKind::Method);
+        assert!(equals.is_const());
+
+        let conversion = methods.get_method("operator bool").unwrap();
+        assert_eq!(conversion.kind(), MethodKind::Conversion);
+
+        assert_eq!(methods.get_method("useOther").unwrap().access(), Access::Public);
+        assert_eq!(methods.get_method("helper").unwrap().access(), Access::Protected);
+    }
+
+    #[test]
+    fn test_vtable() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/class/vtable.hpp").unwrap();
+        assert_eq!(types.len(), 3);
+
+        let TypeKind::Class(animal) = types.get("Animal").unwrap() else {
+            panic!("Expected Class type");
+        };
+        assert_eq!(animal.vtable_pointer_offset(), Some(0));
+        let vtable = animal.vtable().unwrap();
+        assert!(vtable.secondary().is_empty());
+        let entries = vtable.entries();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].name(), "~Animal");
+        assert_eq!(entries[0].kind(), VTableEntryKind::CompleteDestructor);
+        assert_eq!(entries[1].name(), "~Animal");
+        assert_eq!(entries[1].kind(), VTableEntryKind::DeletingDestructor);
+        assert_eq!(entries[2].name(), "speak");
+        assert!(entries[2].is_pure_virtual());
+        assert_eq!(entries[3].name(), "legs");
+        assert_eq!(entries[3].index(), 3);
+
+        let TypeKind::Class(dog) = types.get("Dog").unwrap() else {
+            panic!("Expected Class type");
+        };
+        assert_eq!(dog.fields()[0].offset_bytes(), 28);
+        let vtable = dog.vtable().unwrap();
+        let entries = vtable.entries();
+        let names = entries.iter().map(|e| (e.class_name(), e.name())).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                ("Dog", "~Dog"),
+                ("Dog", "~Dog"),
+                ("Dog", "speak"),
+                ("Animal", "legs"),
+                ("Dog", "name"),
+                ("Dog", "fetch"),
+            ]
+        );
+        assert!(entries.iter().all(|e| e.thunk().is_none()));
+        assert!(!vtable.get_entry("speak").unwrap().is_pure_virtual());
+
+        assert_eq!(vtable.secondary().len(), 1);
+        let named = &vtable.secondary()[0];
+        assert_eq!(named.base_name(), "Named");
+        assert_eq!(named.offset(), 16);
+        assert!(!named.is_virtual());
+        assert_eq!(named.entries().len(), 2);
+        assert_eq!(named.entries()[0].class_name(), "Dog");
+        assert_eq!(named.entries()[0].name(), "name");
+        let thunk = named.entries()[0].thunk().unwrap();
+        assert_eq!(thunk.this_adjustment(), -16);
+        assert!(!thunk.is_virtual());
+        assert_eq!(named.entries()[1].class_name(), "Named");
+        assert_eq!(named.entries()[1].name(), "rename");
+        assert!(named.entries()[1].thunk().is_none());
+    }
+
+    #[test]
+    fn test_covariant_returns() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/class/covariant.hpp").unwrap();
+        assert_eq!(types.len(), 6);
+        let vtable = |name: &str| types.get(name).unwrap().as_struct(&types).unwrap().vtable();
+
+        // The returned class starts with the base, so the pointer needs no adjustment
+        let circle = vtable("Circle").unwrap();
+        assert_eq!(circle.entries().len(), 1);
+        assert_eq!(circle.entries()[0].class_name(), "Circle");
+        assert!(circle.entries()[0].thunk().is_none());
+
+        // The slot of the primary base adjusts the return value, and the override gets a new slot
+        let canvas = vtable("Canvas").unwrap();
+        assert_eq!(canvas.entries().len(), 2);
+        let thunk = canvas.entries()[0].thunk().unwrap();
+        assert_eq!(thunk.this_adjustment(), 0);
+        let adjustment = thunk.return_adjustment().unwrap();
+        assert_eq!(adjustment.offset(), 16);
+        assert!(!adjustment.is_virtual());
+        assert_eq!(canvas.entries()[0].to_string(), "[0] Canvas::clone [return adjustment: 16]");
+        assert_eq!(canvas.entries()[1].class_name(), "Canvas");
+        assert!(canvas.entries()[1].thunk().is_none());
+
+        let square = vtable("Square").unwrap();
+        let names = square.entries().iter().map(|e| e.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["~Square", "~Square", "clone"]);
+        assert!(square.entries().iter().all(|e| e.thunk().is_none()));
+        let shape = &square.secondary()[0];
+        assert_eq!(shape.offset(), 16);
+        let thunk = shape.entries()[0].thunk().unwrap();
+        assert_eq!(thunk.this_adjustment(), -16);
+        assert_eq!(thunk.return_adjustment().unwrap().offset(), 16);
+
+        // The returned class reaches the base through a virtual base
+        let sphere = vtable("Sphere").unwrap();
+        assert_eq!(sphere.entries().len(), 1);
+        let shape = &sphere.secondary()[0];
+        assert!(shape.is_virtual());
+        let thunk = shape.entries()[0].thunk().unwrap();
+        assert_eq!(thunk.this_adjustment(), -8);
+        assert!(thunk.is_virtual());
+        let adjustment = thunk.return_adjustment().unwrap();
+        assert_eq!(adjustment.offset(), 0);
+        assert!(adjustment.is_virtual());
+    }
+
+    #[test]
+    fn test_virtual_inheritance() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/class/virtual_inheritance.hpp").unwrap();
+        assert_eq!(types.len(), 8);
+
+        // A nearly empty virtual base is the primary base, and shares the vtable pointer
+        let TypeKind::Struct(b) = types.get("B").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(b.size(), 16);
+        assert_eq!(b.fields()[0].offset_bytes(), 8);
+        assert!(b.base_types()[0].is_virtual());
+        assert_eq!(b.base_types()[0].offset(), 0);
+        let vtable = b.vtable().unwrap();
+        assert_eq!(vtable.virtual_base_offsets().len(), 1);
+        assert_eq!(vtable.virtual_base_offsets()[0].base_name(), "A");
+        assert_eq!(vtable.virtual_base_offsets()[0].offset(), 0);
+        assert_eq!(vtable.entries().len(), 1);
+        assert_eq!(vtable.entries()[0].class_name(), "B");
+        assert_eq!(vtable.entries()[0].name(), "f");
+        assert!(vtable.entries()[0].thunk().is_none());
+        assert!(vtable.secondary().is_empty());
+
+        let TypeKind::Struct(c) = types.get("C").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(c.size(), 16);
+        assert_eq!(c.fields()[0].offset_bytes(), 12);
+        assert_eq!(c.virtual_bases()[0].name(), "A");
+        assert_eq!(c.virtual_bases()[0].offset(), 0);
+        let vtable = c.vtable().unwrap();
+        let names = vtable.entries().iter().map(|e| (e.class_name(), e.name())).collect::<Vec<_>>();
+        assert_eq!(names, [("B", "f"), ("C", "g")]);
+        assert!(vtable.secondary().is_empty());
+
+        // The virtual base is shared with the first base which has it as primary base
+        let TypeKind::Struct(d) = types.get("D").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(d.size(), 32);
+        assert_eq!(d.base_types()[0].offset(), 0);
+        assert_eq!(d.base_types()[1].offset(), 16);
+        assert_eq!(d.fields()[0].offset_bytes(), 28);
+        assert_eq!(d.virtual_bases()[0].name(), "A");
+        assert_eq!(d.virtual_bases()[0].offset(), 0);
+        let vtable = d.vtable().unwrap();
+        assert_eq!(vtable.virtual_base_offsets()[0].offset(), 0);
+        assert_eq!(vtable.secondary().len(), 1);
+        let r = &vtable.secondary()[0];
+        assert_eq!(r.base_name(), "R");
+        assert_eq!(r.offset(), 16);
+        assert_eq!(r.virtual_base_offsets().len(), 1);
+        assert_eq!(r.virtual_base_offsets()[0].base_name(), "A");
+        assert_eq!(r.virtual_base_offsets()[0].offset(), -16);
+
+        // A virtual base with data members gets its own vtable, with virtual thunks
+        let TypeKind::Struct(w) = types.get("W").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(w.size(), 32);
+        assert_eq!(w.fields()[0].offset_bytes(), 8);
+        assert_eq!(w.base_types()[0].offset(), 16);
+        assert_eq!(w.get_field(&types, "v").unwrap().offset_bytes(), 24);
+        let vtable = w.vtable().unwrap();
+        assert_eq!(vtable.virtual_base_offsets()[0].base_name(), "V");
+        assert_eq!(vtable.virtual_base_offsets()[0].offset(), 16);
+        assert_eq!(vtable.entries().len(), 1);
+        assert_eq!(vtable.entries()[0].class_name(), "W");
+        assert_eq!(vtable.secondary().len(), 1);
+        let v = &vtable.secondary()[0];
+        assert_eq!(v.base_name(), "V");
+        assert_eq!(v.offset(), 16);
+        assert!(v.is_virtual());
+        assert!(v.virtual_base_offsets().is_empty());
+        assert_eq!(v.entries()[0].class_name(), "W");
+        let thunk = v.entries()[0].thunk().unwrap();
+        assert_eq!(thunk.this_adjustment(), -16);
+        assert!(thunk.is_virtual());
+    }
+
+    #[test]
+    fn test_members() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/class/members.hpp").unwrap();
+        assert_eq!(types.len(), 1);
+
+        let TypeKind::Class(members) = types.get("Members").unwrap() else {
+            panic!("Expected Class type");
+        };
+        assert_eq!(members.fields().len(), 3);
+        assert_eq!(members.fields()[0].name(), Some("publicValue"));
+        assert_eq!(members.fields()[0].access(), Access::Public);
+        assert_eq!(members.fields()[1].name(), Some("protectedValue"));
+        assert_eq!(members.fields()[1].access(), Access::Protected);
+        assert_eq!(members.fields()[2].name(), Some("privateValue"));
+        assert_eq!(members.fields()[2].access(), Access::Private);
+
+        assert_eq!(members.static_members().len(), 4);
+        let max_count = members.get_static_member("MAX_COUNT").unwrap();
+        assert_eq!(max_count.kind(), &TypeKind::S32);
+        assert!(max_count.constant());
+        assert_eq!(max_count.access(), Access::Public);
+        assert_eq!(max_count.value(), Some(&ConstantValue::Signed(16)));
+
+        let scale = members.get_static_member("SCALE").unwrap();
+        assert_eq!(scale.kind(), &TypeKind::F32);
+        assert_eq!(scale.value().and_then(|v| v.as_f64()), Some(1.5));
+
+        let instance = members.get_static_member("instance").unwrap();
+        assert!(matches!(instance.kind(), TypeKind::Pointer { .. }));
+        assert!(!instance.constant());
+        assert_eq!(instance.value(), None);
+
+        let mask = members.get_static_member("MASK").unwrap();
+        assert_eq!(mask.access(), Access::Private);
+        assert_eq!(mask.value(), Some(&ConstantValue::Unsigned(0xffffffff00000000)));
+    }
 }
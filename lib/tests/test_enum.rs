@@ -26,6 +26,7 @@ mod tests {
         assert_eq!(enum_decl.constants()[0].value(), 0);
         assert_eq!(enum_decl.constants()[1].value(), 1);
         assert_eq!(enum_decl.constants()[2].value(), 2);
+        assert_eq!(enum_decl.constants()[0].expression(), None);
     }
 
     #[test]
@@ -115,5 +116,162 @@ mod tests {
         assert_eq!(thing_enum.get("Thing3").unwrap().value(), 300);
         assert_eq!(thing_enum.get("Thing3a").unwrap().value(), 301);
         assert_eq!(thing_enum.get("WeirdThing").unwrap().value(), 641);
+
+        let flag2 = flags_enum.get("Flag2").unwrap();
+        assert_eq!(flag2.expression(), Some("1 << 1"));
+        assert!(flag2.macros().is_empty());
+
+        let thing2 = thing_enum.get("Thing2").unwrap();
+        assert_eq!(thing2.expression(), Some("GROUP_SIZE * 2"));
+        assert_eq!(thing2.macros(), ["GROUP_SIZE"]);
+
+        let thing3a = thing_enum.get("Thing3a").unwrap();
+        assert_eq!(thing3a.expression(), Some("Thing3 + 1"));
+        assert!(thing3a.macros().is_empty());
+
+        let weird_thing = thing_enum.get("WeirdThing").unwrap();
+        assert_eq!(weird_thing.expression(), Some("(Thing1 * 3 + Thing2 * 7 + Thing3a * 5) / 5"));
+    }
+
+    #[test]
+    fn test_underlying_type() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/enum/underlying.hpp").unwrap();
+        let get = |name: &str| {
+            let TypeKind::Enum(enum_decl) = types.get(name).unwrap() else {
+                panic!("Expected Enum type");
+            };
+            enum_decl
+        };
+
+        let plain = get("Plain");
+        assert_eq!(plain.underlying_type(), &TypeKind::U8);
+        assert!(!plain.is_signed());
+        assert!(!plain.is_scoped());
+        assert!(!plain.has_fixed_underlying_type());
+
+        let negative = get("Negative");
+        assert_eq!(negative.underlying_type(), &TypeKind::S8);
+        assert!(negative.is_signed());
+
+        let fixed = get("Fixed");
+        assert_eq!(fixed.underlying_type(), &TypeKind::U8);
+        assert!(fixed.has_fixed_underlying_type());
+        assert!(!fixed.is_scoped());
+
+        let scoped = get("Scoped");
+        assert!(scoped.is_scoped());
+        assert!(scoped.has_fixed_underlying_type());
+        assert_eq!(scoped.underlying_type(), &TypeKind::S32);
+        assert_eq!(scoped.size(), 4);
+        assert_eq!(scoped.alignment(), 4);
+
+        let scoped_fixed = get("ScopedFixed");
+        assert_eq!(scoped_fixed.underlying_type(), &TypeKind::S64);
+        assert_eq!(scoped_fixed.size(), 8);
+        assert_eq!(scoped_fixed.alignment(), 8);
+    }
+
+    #[test]
+    fn test_unsigned_values() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/enum/unsigned.hpp").unwrap();
+        let get = |name: &str| {
+            let TypeKind::Enum(enum_decl) = types.get(name).unwrap() else {
+                panic!("Expected Enum type");
+            };
+            enum_decl
+        };
+
+        let big = get("Big");
+        let big_max = big.get("BIG_MAX").unwrap();
+        assert!(!big_max.is_signed());
+        assert_eq!(big_max.unsigned_value(), u64::MAX);
+        assert_eq!(big_max.to_string(), "BIG_MAX: 0xffffffffffffffff");
+        assert_eq!(big.get_by_unsigned_value(u64::MAX).unwrap().name(), "BIG_MAX");
+
+        let word_max = get("Word").get("WORD_MAX").unwrap();
+        assert_eq!(word_max.value(), 0xFFFFFFFF);
+        assert_eq!(word_max.unsigned_value(), 0xFFFFFFFF);
+        assert_eq!(word_max.to_string(), "WORD_MAX: 0xffffffff");
+
+        let minus_one = get("Signed").get("SIGNED_MINUS_ONE").unwrap();
+        assert!(minus_one.is_signed());
+        assert_eq!(minus_one.value(), -1);
+        assert_eq!(minus_one.to_string(), "SIGNED_MINUS_ONE: -0x1");
+    }
+
+    #[test]
+    fn test_flags() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/enum/flags.h").unwrap();
+        let get = |name: &str| {
+            let TypeKind::Enum(enum_decl) = types.get(name).unwrap() else {
+                panic!("Expected Enum type");
+            };
+            enum_decl
+        };
+
+        assert!(get("Permissions").is_flag_enum());
+        assert!(!get("Sequential").is_flag_enum());
+
+        let style = get("Style");
+        assert!(style.is_flag_enum());
+        assert_eq!(style.format_flags(0), "STYLE_NONE");
+        assert_eq!(style.format_flags(0b101), "STYLE_BOLD | STYLE_UNDERLINE");
+        assert_eq!(style.format_flags(0b111), "STYLE_ALL");
+        assert_eq!(style.format_flags(0x41), "STYLE_BOLD | 0x40");
+        let (flags, remaining) = style.decompose_flags(0x43);
+        assert_eq!(
+            flags.iter().map(|c| c.name()).collect::<Vec<_>>(),
+            ["STYLE_BOLD", "STYLE_ITALIC"]
+        );
+        assert_eq!(remaining, 0x40);
+
+        assert_eq!(style.parse_flags("STYLE_BOLD | STYLE_UNDERLINE | 0x40"), Some(0x45));
+        assert_eq!(style.parse_flags("STYLE_NONE"), Some(0));
+        assert_eq!(style.parse_flags("STYLE_BOLD | STYLE_MISSING"), None);
+
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/enum/expr.h").unwrap();
+        let TypeKind::Enum(flags_enum) = types.get("Flags").unwrap() else {
+            panic!("Expected Enum type");
+        };
+        assert!(flags_enum.is_flag_enum());
+        assert_eq!(flags_enum.format_flags(0x45), "Flag1 | Flag3 | 0x40");
+        let TypeKind::Enum(thing_enum) = types.get("Thing").unwrap() else {
+            panic!("Expected Enum type");
+        };
+        assert!(!thing_enum.is_flag_enum());
+    }
+
+    #[test]
+    fn test_lookup() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/enum/lookup.h").unwrap();
+
+        assert_eq!(types.anonymous_enums().len(), 1);
+        let (item_enum, sword) = types.get_enum_constant("ITEM_SWORD").unwrap();
+        assert_eq!(item_enum.name(), None);
+        assert_eq!(sword.value(), 1);
+
+        let (actor_kind, hero) = types.get_enum_constant("ACTOR_HERO").unwrap();
+        assert_eq!(actor_kind.name(), Some("ActorKind"));
+        assert_eq!(hero.value(), 0);
+        assert!(types.get_enum_constant("ACTOR_MISSING").is_none());
+
+        let players = types.get_enum_constants_by_value("ActorKind", 0);
+        assert_eq!(
+            players.iter().map(|c| c.name()).collect::<Vec<_>>(),
+            ["ACTOR_PLAYER", "ACTOR_HERO"]
+        );
+        assert_eq!(types.format_enum_value("ActorKind", 0).as_deref(), Some("ACTOR_PLAYER"));
+        assert_eq!(types.format_enum_value("ActorKindAlias", 1).as_deref(), Some("ACTOR_ENEMY"));
+        assert_eq!(types.format_enum_value("ActorKind", 7).as_deref(), Some("7"));
+        assert_eq!(
+            types.format_enum_value("StateFlags", 0b101).as_deref(),
+            Some("STATE_FROZEN | STATE_BURNING")
+        );
+        assert_eq!(types.format_enum_value("Missing", 0), None);
     }
 }
@@ -0,0 +1,268 @@
+#[cfg(test)]
+mod tests {
+    use type_crawler::{
+        AttributeKind, CallingConvention, ConstantValue, Env, EnvOptions, StorageClass,
+        TypeCrawler, TypeKind,
+    };
+
+    #[test]
+    fn test_declarations() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/global/declarations.hpp").unwrap();
+        assert_eq!(types.len(), 4);
+
+        let TypeKind::Struct(in_namespace) = types.get("InNamespace").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(in_namespace.fields().len(), 1);
+
+        let TypeKind::Struct(in_inline_namespace) = types.get("InInlineNamespace").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(in_inline_namespace.fields().len(), 1);
+
+        let TypeKind::Typedef(aliased_int) = types.get("AliasedInt").unwrap() else {
+            panic!("Expected Typedef type");
+        };
+        assert_eq!(aliased_int.underlying_type(), &TypeKind::S32);
+
+        assert!(matches!(types.get("WithMethods"), Some(TypeKind::Class(_))));
+        assert!(types.get("Box").is_none());
+        assert!(types.get("MyStruct").is_none());
+    }
+
+    #[test]
+    fn test_macro_constants() {
+        let mut crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/global/macros.h").unwrap();
+        assert_eq!(types.macro_constants().count(), 0);
+
+        crawler.set_collect_macros(true);
+        let types = crawler.parse_file("tests/global/macros.h").unwrap();
+        assert_eq!(types.macro_constants().count(), 15);
+
+        let max_actors = types.get_macro_constant("MAX_ACTORS").unwrap();
+        assert_eq!(max_actors.value(), &ConstantValue::Signed(64));
+        assert_eq!(max_actors.body(), "64");
+        assert_eq!(max_actors.location().line(), 4);
+        assert!(max_actors.location().file().ends_with("macros.h"));
+
+        let half_actors = types.get_macro_constant("HALF_ACTORS").unwrap();
+        assert_eq!(half_actors.value(), &ConstantValue::Signed(32));
+        assert_eq!(half_actors.body(), "(MAX_ACTORS / 2)");
+
+        let mask = types.get_macro_constant("MASK").unwrap();
+        assert_eq!(mask.value(), &ConstantValue::Unsigned(0xFFFFFFFF));
+        assert_eq!(
+            types.get_macro_constant("GRAVITY").unwrap().value(),
+            &ConstantValue::Float(9.5)
+        );
+        assert_eq!(
+            types.get_macro_constant("PLAYER_NAME").unwrap().value(),
+            &ConstantValue::String("player".to_string())
+        );
+        assert_eq!(types.get_macro_constant("NEGATIVE").unwrap().value().as_i64(), Some(-5));
+
+        // Macros are expanded as tokens, not as values
+        assert_eq!(types.get_macro_constant("SUM").unwrap().value(), &ConstantValue::Signed(3));
+        assert_eq!(types.get_macro_constant("PRODUCT").unwrap().value(), &ConstantValue::Signed(7));
+        assert_eq!(types.get_macro_constant("BYTE").unwrap().value(), &ConstantValue::Signed(0xFF));
+        assert_eq!(types.get_macro_constant("LETTER").unwrap().value(), &ConstantValue::Signed(65));
+        assert_eq!(
+            types.get_macro_constant("GREETING").unwrap().value(),
+            &ConstantValue::String("hello, player".to_string())
+        );
+        assert_eq!(types.get_macro_constant("FORWARD").unwrap().value(), &ConstantValue::Signed(6));
+        let version = types.get_macro_constant("VERSION").unwrap();
+        assert_eq!(version.value(), &ConstantValue::Signed(2));
+        assert_eq!(version.location().line(), 19);
+        assert!(types.get_macro_constant("SELF").is_none());
+        // Unsigned arithmetic wraps around
+        assert_eq!(
+            types.get_macro_constant("WRAPPED").unwrap().value(),
+            &ConstantValue::Unsigned(1)
+        );
+
+        assert!(types.get_macro_constant("MACROS_H").is_none());
+        assert!(types.get_macro_constant("SQUARE").is_none());
+        assert!(types.get_macro_constant("u32").is_none());
+        assert!(types.get_macro_constant("UNUSED").is_none());
+    }
+
+    #[test]
+    fn test_included_macro_constants() {
+        let mut crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        crawler.set_collect_macros(true);
+        let types = crawler.parse_file("tests/global/included_macros.h").unwrap();
+
+        // Macros of included headers are expanded, but not reported
+        assert_eq!(types.macro_constants().count(), 1);
+        assert_eq!(types.get_macro_constant("DERIVED").unwrap().value(), &ConstantValue::Signed(5));
+        assert!(types.get_macro_constant("BASE_COUNT").is_none());
+    }
+
+    #[test]
+    fn test_constants() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/global/constants.hpp").unwrap();
+
+        let max_hp = types.get_constant("kMaxHp").unwrap();
+        assert_eq!(max_hp.value(), &ConstantValue::Signed(100));
+        assert_eq!(max_hp.kind(), &TypeKind::S32);
+        assert!(!max_hp.is_constexpr());
+        assert_eq!(max_hp.location().line(), 3);
+
+        let flags = types.get_constant("FLAGS").unwrap();
+        assert!(flags.is_constexpr());
+        assert_eq!(flags.value().as_u64(), Some(17));
+        assert_eq!(flags.kind(), &TypeKind::Named("u32".to_string()));
+
+        assert_eq!(types.get_constant("kGravity").unwrap().value(), &ConstantValue::Float(9.5));
+
+        let big = types.get_constant("game::kBig").unwrap();
+        assert_eq!(big.name(), "kBig");
+        assert_eq!(big.qualified_name(), "game::kBig");
+        assert_eq!(big.value().as_i64(), Some(1 << 40));
+        assert_eq!(types.get_constant("kBig").unwrap().qualified_name(), "game::kBig");
+
+        let sword = types.get_constant("ITEM_SWORD").unwrap();
+        assert_eq!(sword.value().as_u64(), Some(1));
+        assert!(types.get_constant("ITEM_NONE").is_some());
+
+        assert!(types.get_constant("notConstant").is_none());
+        assert!(types.get_constant("kExternal").is_none());
+    }
+
+    #[test]
+    fn test_variables() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/global/variables.hpp").unwrap();
+
+        let player = types.get_variable("gPlayer").unwrap();
+        assert_eq!(player.storage_class(), StorageClass::Extern);
+        assert!(!player.is_definition());
+        let TypeKind::Pointer { pointee_type, .. } = player.kind() else {
+            panic!("Expected Pointer type");
+        };
+        assert_eq!(pointee_type.as_ref(), &TypeKind::Named("Actor".to_string()));
+        assert_eq!(player.location().line(), 7);
+
+        let save_data = types.get_variable("gSaveData").unwrap();
+        let TypeKind::Array { element_type, size } = save_data.kind() else {
+            panic!("Expected Array type");
+        };
+        assert_eq!(element_type.as_ref(), &TypeKind::Named("u8".to_string()));
+        assert_eq!(*size, Some(0x1000));
+
+        let frame_count = types.get_variable("sFrameCount").unwrap();
+        assert_eq!(frame_count.storage_class(), StorageClass::Static);
+        assert_eq!(frame_count.kind(), &TypeKind::S32);
+
+        let score = types.get_variable("gScore").unwrap();
+        assert_eq!(score.storage_class(), StorageClass::None);
+        assert!(score.is_definition());
+
+        let lives = types.get_variable("gLives").unwrap();
+        assert!(lives.is_definition());
+        assert_eq!(lives.location().line(), 12);
+
+        assert_eq!(types.get_variable("gDebugFlags").unwrap().section(), Some(".sbss"));
+        assert_eq!(types.get_variable("gScore").unwrap().section(), None);
+
+        let actors = types.get_variable("game::gActors").unwrap();
+        assert_eq!(actors.name(), "gActors");
+        assert!(matches!(actors.kind(), TypeKind::Array { size: None, .. }));
+
+        assert_eq!(types.variables().count(), 7);
+    }
+
+    #[test]
+    fn test_unsupported_variables() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/global/unsupported.hpp").unwrap();
+
+        // Deduced types are resolved
+        let auto = types.get_constant("kAuto").unwrap();
+        assert_eq!(auto.kind(), &TypeKind::S32);
+        assert_eq!(auto.value(), &ConstantValue::Signed(1));
+
+        // Declarations of unsupported types are left out without failing the parse
+        assert!(types.get_variable("gNull").is_none());
+        assert!(types.get_variable("gVector").is_none());
+        assert!(types.get_variable("gWide").is_none());
+        assert!(types.get_variable("gKept").is_some());
+    }
+
+    #[test]
+    fn test_unsupported_functions() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/global/unsupported.hpp").unwrap();
+        assert!(types.get_function("TakeWide").is_none());
+        assert!(types.get_function("Kept").is_some());
+
+        // Functions without prototypes in C
+        let types = crawler.parse_file("tests/global/no_prototype.h").unwrap();
+        let set_callback = types.get_function("SetCallback").unwrap();
+        let TypeKind::Pointer { pointee_type, .. } = set_callback.parameters()[0].kind() else {
+            panic!("Expected Pointer type, found: {:?}", set_callback.parameters()[0].kind());
+        };
+        assert_eq!(
+            **pointee_type,
+            TypeKind::Function { return_type: Box::new(TypeKind::Void), parameters: Vec::new() }
+        );
+        assert!(!types.get_function("NoPrototype").unwrap().has_prototype());
+        assert!(types.get_variable("gComplex").is_none());
+    }
+
+    #[test]
+    fn test_functions() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/global/functions.hpp").unwrap();
+
+        let update = types.get_function("Actor_Update").unwrap();
+        assert_eq!(update.mangled_name(), Some("Actor_Update"));
+        assert_eq!(update.return_type(), &TypeKind::Void);
+        assert_eq!(update.parameters().len(), 2);
+        assert_eq!(update.parameters()[0].name(), Some("actor"));
+        assert!(matches!(update.parameters()[0].kind(), TypeKind::Pointer { .. }));
+        assert_eq!(update.parameters()[1].name(), Some("delta"));
+        assert_eq!(update.parameters()[1].kind(), &TypeKind::F32);
+        assert!(!update.is_variadic());
+        assert!(!update.is_definition());
+        assert_eq!(update.storage_class(), StorageClass::None);
+        assert_eq!(update.calling_convention(), Some(CallingConvention::Cdecl));
+        assert_eq!(update.location().line(), 4);
+
+        let printf = types.get_function("Debug_Printf").unwrap();
+        assert!(printf.is_variadic());
+        assert_eq!(printf.parameters().len(), 1);
+        assert_eq!(printf.return_type(), &TypeKind::S32);
+
+        let clamp = types.get_function("clamp").unwrap();
+        assert!(clamp.is_definition());
+        assert_eq!(clamp.storage_class(), StorageClass::Static);
+        assert_eq!(clamp.parameters().len(), 3);
+
+        let alloc = types.get_function("Alloc").unwrap();
+        assert_eq!(alloc.calling_convention(), Some(CallingConvention::Win64));
+
+        let unnamed = types.get_function("Unnamed").unwrap();
+        assert_eq!(unnamed.parameters()[0].name(), None);
+        assert_eq!(unnamed.parameters()[1].kind(), &TypeKind::S8);
+        assert_eq!(unnamed.mangled_name(), Some("_Z7Unnamedic"));
+
+        let init = types.get_function("Init").unwrap();
+        assert_eq!(init.attributes()[0].kind(), &AttributeKind::Section);
+        assert_eq!(init.attributes()[0].arguments(), [".init"]);
+
+        let overloads = types.get_overloads("game::Reset");
+        assert_eq!(overloads.len(), 2);
+        assert!(overloads[0].parameters().is_empty());
+        assert_eq!(overloads[1].parameters()[0].name(), Some("level"));
+        assert_eq!(types.get_function("Reset").unwrap().qualified_name(), "game::Reset");
+        assert_eq!(
+            types.get_function_by_mangled_name("_ZN4game5ResetEi").unwrap().parameters().len(),
+            1
+        );
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use type_crawler::{Env, EnvOptions, TypeCrawler, TypeKind};
+    use type_crawler::{
+        Access, AttributeKind, CommentStyle, Completeness, Endianness, Env, EnvOptions,
+        OffsetCommentOptions, TypeCrawler, TypeKind,
+    };
 
     #[test]
     fn test_simple() {
@@ -37,15 +40,15 @@ mod tests {
         assert_eq!(bitfield.fields()[0].offset_bytes(), 0);
         assert_eq!(bitfield.fields()[0].offset_bits(), 0);
         assert_eq!(bitfield.fields()[0].bit_field_width(), Some(3));
-        assert_eq!(bitfield.fields()[0].size(&types), 1);
+        assert_eq!(bitfield.fields()[0].size(&types), 4);
         assert_eq!(bitfield.fields()[1].offset_bytes(), 0);
         assert_eq!(bitfield.fields()[1].offset_bits(), 3);
         assert_eq!(bitfield.fields()[1].bit_field_width(), Some(5));
-        assert_eq!(bitfield.fields()[1].size(&types), 1);
+        assert_eq!(bitfield.fields()[1].size(&types), 4);
         assert_eq!(bitfield.fields()[2].offset_bytes(), 1);
         assert_eq!(bitfield.fields()[2].offset_bits(), 8);
         assert_eq!(bitfield.fields()[2].bit_field_width(), Some(2));
-        assert_eq!(bitfield.fields()[2].size(&types), 1);
+        assert_eq!(bitfield.fields()[2].size(&types), 4);
     }
 
     #[test]
@@ -73,7 +76,9 @@ mod tests {
         };
         assert_eq!(derived.size(), 8);
         assert_eq!(derived.alignment(), 4);
-        assert_eq!(derived.base_types(), &["Base"]);
+        assert_eq!(derived.base_types().len(), 1);
+        assert_eq!(derived.base_types()[0].name(), "Base");
+        assert_eq!(derived.base_types()[0].offset(), 0);
         assert_eq!(derived.fields().len(), 1);
         assert_eq!(derived.fields()[0].name(), Some("derivedValue"));
         assert_eq!(derived.fields()[0].offset_bytes(), 4);
@@ -88,6 +93,149 @@ mod tests {
         assert_eq!(derived_field.kind(), &TypeKind::S32);
     }
 
+    #[test]
+    fn test_multiple_inheritance() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/multiple_inheritance.hpp").unwrap();
+        assert_eq!(types.len(), 7);
+
+        let TypeKind::Struct(c) = types.get("C").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(c.base_types().len(), 2);
+        assert_eq!(c.base_types()[0].name(), "A");
+        assert_eq!(c.base_types()[0].offset(), 0);
+        assert_eq!(c.base_types()[0].access(), Access::Public);
+        assert_eq!(c.base_types()[1].name(), "B");
+        assert_eq!(c.base_types()[1].offset(), 8);
+        assert_eq!(c.base_types()[1].access(), Access::Private);
+        assert!(c.virtual_bases().is_empty());
+        assert_eq!(c.fields()[0].offset_bytes(), 16);
+        assert_eq!(c.get_field(&types, "a").unwrap().offset_bytes(), 0);
+        assert_eq!(c.get_field(&types, "b").unwrap().offset_bytes(), 8);
+        assert_eq!(c.get_field(&types, "c").unwrap().offset_bytes(), 16);
+
+        let TypeKind::Struct(l) = types.get("L").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(l.size(), 16);
+        assert!(l.base_types()[0].is_virtual());
+        assert_eq!(l.base_types()[0].offset(), 12);
+        assert_eq!(l.get_field(&types, "v").unwrap().offset_bytes(), 12);
+
+        let TypeKind::Struct(d) = types.get("D").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(d.size(), 40);
+        assert_eq!(d.base_types().len(), 2);
+        assert_eq!(d.base_types()[0].name(), "L");
+        assert_eq!(d.base_types()[0].offset(), 0);
+        assert!(!d.base_types()[0].is_virtual());
+        assert_eq!(d.base_types()[1].name(), "R");
+        assert_eq!(d.base_types()[1].offset(), 16);
+        assert_eq!(d.virtual_bases().len(), 1);
+        assert_eq!(d.virtual_bases()[0].name(), "V");
+        assert_eq!(d.virtual_bases()[0].offset(), 32);
+        assert_eq!(d.fields()[0].offset_bytes(), 28);
+        assert_eq!(d.get_field(&types, "l").unwrap().offset_bytes(), 8);
+        assert_eq!(d.get_field(&types, "r").unwrap().offset_bytes(), 24);
+        assert_eq!(d.get_field(&types, "v").unwrap().offset_bytes(), 32);
+    }
+
+    #[test]
+    fn test_namespaced_bases() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/namespaced_bases.hpp").unwrap();
+        assert_eq!(types.len(), 3);
+
+        // Bases are matched by their declaration, however they are spelled
+        let TypeKind::Struct(y) = types.get("Y").unwrap() else {
+            panic!("Expected Struct type");
+        };
+        assert_eq!(y.size(), 24);
+        assert_eq!(y.base_types()[0].name(), "ns::X");
+        assert_eq!(y.virtual_bases()[0].offset(), 16);
+        assert_eq!(y.get_field(&types, "x").unwrap().offset_bytes(), 8);
+        assert_eq!(y.get_field(&types, "y").unwrap().offset_bytes(), 12);
+        assert_eq!(y.get_field(&types, "v").unwrap().offset_bytes(), 16);
+
+        assert_eq!(y.base_types()[0].decl_name(), "X");
+        assert_eq!(types.ancestors("Y"), ["X", "V"]);
+        assert!(types.is_derived_from("Y", "X"));
+        assert!(types.is_derived_from("Y", "V"));
+        assert_eq!(types.direct_derived_types("X"), ["Y"]);
+        assert_eq!(types.derived_types("V"), ["X", "Y"]);
+        assert_eq!(types.hierarchy_root("Y"), "V");
+    }
+
+    #[test]
+    fn test_hierarchy() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/multiple_inheritance.hpp").unwrap();
+
+        assert_eq!(types.ancestors("C"), ["A", "B"]);
+        assert_eq!(types.ancestors("D"), ["L", "R", "V"]);
+        assert!(types.ancestors("A").is_empty());
+
+        assert_eq!(types.direct_derived_types("V"), ["L", "R"]);
+        assert_eq!(types.derived_types("V"), ["L", "R", "D"]);
+        assert!(types.derived_types("D").is_empty());
+
+        assert_eq!(types.hierarchy_root("D"), "V");
+        assert_eq!(types.hierarchy_root("C"), "A");
+        assert_eq!(types.hierarchy_root("A"), "A");
+
+        assert!(types.is_derived_from("D", "V"));
+        assert!(types.is_derived_from("C", "B"));
+        assert!(!types.is_derived_from("C", "V"));
+        assert!(!types.is_derived_from("D", "D"));
+    }
+
+    #[test]
+    fn test_traits() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/traits.hpp").unwrap();
+        let get = |name: &str| types.get(name).unwrap().as_struct(&types).unwrap().traits();
+
+        let empty = get("Empty");
+        assert!(empty.is_empty());
+        assert!(empty.is_pod());
+        assert!(empty.is_standard_layout());
+        assert!(empty.is_trivially_copyable());
+
+        let plain = get("Plain");
+        assert!(!plain.is_empty());
+        assert!(!plain.is_packed());
+        assert!(!plain.has_explicit_alignment());
+        assert!(!plain.is_polymorphic());
+        assert!(plain.is_pod());
+        assert!(plain.is_standard_layout());
+        assert!(plain.is_trivially_copyable());
+
+        assert!(get("Packed").is_packed());
+        assert!(get("Aligned").has_explicit_alignment());
+
+        let mixed_access = get("MixedAccess");
+        assert!(!mixed_access.is_standard_layout());
+        assert!(!mixed_access.is_pod());
+
+        assert!(get("Copyable").is_trivially_copyable());
+        assert!(!get("NotCopyable").is_trivially_copyable());
+        assert!(get("IntAssignable").is_trivially_copyable());
+        assert!(!get("CopyAssignable").is_trivially_copyable());
+
+        let abstract_ = get("Abstract");
+        assert!(abstract_.is_polymorphic());
+        assert!(abstract_.is_abstract());
+        assert!(!abstract_.is_trivially_copyable());
+        assert!(!abstract_.is_empty());
+
+        let concrete = get("Concrete");
+        assert!(concrete.is_polymorphic());
+        assert!(!concrete.is_abstract());
+        assert!(!concrete.is_standard_layout());
+    }
+
     #[test]
     fn test_basic_types() {
         let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
@@ -154,6 +302,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_references() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/references.hpp").unwrap();
+
+        let references = types.get("References").unwrap().as_struct(&types).unwrap();
+        assert_eq!(references.size(), 24);
+        // References occupy a pointer, whatever the size of the referenced type
+        for name in ["ch", "value", "moved"] {
+            assert_eq!(references.get_field(&types, name).unwrap().size(&types), 8);
+        }
+
+        // References to incomplete types have a size too
+        let take = references.get_method("take").unwrap();
+        let TypeKind::Function { parameters, .. } = take.signature() else {
+            panic!("Expected Function type, found: {:?}", take.signature());
+        };
+        assert!(matches!(parameters[0], TypeKind::Reference { size: 8, .. }));
+    }
+
     #[test]
     fn test_forward_decl() {
         let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
@@ -196,4 +364,376 @@ mod tests {
         assert_eq!(**element_type, TypeKind::S32);
         assert_eq!(*size, None);
     }
+
+    #[test]
+    fn test_attributes() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/attributes.h").unwrap();
+
+        let node = types.get("Node").unwrap().as_struct(&types).unwrap();
+        assert_eq!(node.attributes().len(), 1);
+        assert_eq!(node.attributes()[0].kind(), &AttributeKind::Aligned);
+        assert_eq!(node.attributes()[0].arguments(), ["8"]);
+
+        let owner = node.get_field(&types, "owner").unwrap();
+        assert_eq!(owner.attributes().len(), 1);
+        assert_eq!(owner.attributes()[0].kind(), &AttributeKind::Annotate);
+        assert_eq!(owner.attributes()[0].arguments(), ["ptr:Actor"]);
+        assert_eq!(owner.attributes()[0].to_string(), "annotate(ptr:Actor)");
+
+        let legacy_flags = node.get_field(&types, "legacyFlags").unwrap();
+        assert_eq!(legacy_flags.attributes()[0].kind(), &AttributeKind::Deprecated);
+        assert_eq!(legacy_flags.attributes()[0].arguments(), ["use flags instead"]);
+        assert!(node.get_field(&types, "flags").unwrap().attributes().is_empty());
+
+        let packed_header = types.get("PackedHeader").unwrap();
+        assert_eq!(packed_header.attributes()[0].kind(), &AttributeKind::Packed);
+
+        let config = types.get("Config").unwrap();
+        assert_eq!(config.attributes()[0].kind(), &AttributeKind::Section);
+        assert_eq!(config.attributes()[0].arguments(), [".data"]);
+
+        let object_id = types.get("ObjectId").unwrap();
+        assert_eq!(object_id.attributes()[0].kind(), &AttributeKind::Annotate);
+        assert_eq!(object_id.attributes()[0].arguments(), ["id"]);
+
+        let TypeKind::Enum(state) = types.get("State").unwrap() else {
+            panic!("Expected Enum type");
+        };
+        assert!(state.constants()[0].attributes().is_empty());
+        assert_eq!(state.constants()[1].attributes()[0].kind(), &AttributeKind::Deprecated);
+    }
+
+    #[test]
+    fn test_pack() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/pack.h").unwrap();
+        let get = |name: &str| types.get(name).unwrap().as_struct(&types).unwrap();
+
+        let packed1 = get("Packed1");
+        assert_eq!(packed1.pack(), Some(1));
+        assert!(packed1.attributes().is_empty());
+        assert_eq!(packed1.size(), 7);
+        let length = packed1.get_field(&types, "length").unwrap();
+        assert_eq!(length.offset_bytes(), 1);
+        assert_eq!(length.alignment(), 1);
+        assert_eq!(length.natural_alignment(), 4);
+        assert!(length.has_alignment_override());
+        assert!(!packed1.get_field(&types, "tag").unwrap().has_alignment_override());
+
+        let packed2 = get("Packed2");
+        assert_eq!(packed2.pack(), Some(2));
+        assert_eq!(packed2.get_field(&types, "length").unwrap().offset_bytes(), 2);
+        assert_eq!(packed2.get_field(&types, "length").unwrap().alignment(), 2);
+
+        // The pragma is reported even when it doesn't change the layout
+        assert_eq!(get("NoEffect").pack(), Some(8));
+        assert_eq!(get("NoEffectTight").pack(), Some(2));
+        assert_eq!(get("NoEffectTight").size(), 4);
+        assert_eq!(get("NoEffectTight").get_field(&types, "length").unwrap().alignment(), 2);
+        let packed4_aligned = get("Packed4Aligned");
+        assert_eq!(packed4_aligned.pack(), Some(4));
+        assert_eq!(packed4_aligned.alignment(), 16);
+        assert_eq!(packed4_aligned.get_field(&types, "value").unwrap().alignment(), 4);
+        assert_eq!(get("RestoredPack").pack(), Some(2));
+        assert_eq!(get("RestoredPack").get_field(&types, "length").unwrap().offset_bytes(), 2);
+        assert_eq!(get("ResetPack").pack(), None);
+        assert_eq!(get("Unpacked").pack(), None);
+        assert!(!get("Unpacked").get_field(&types, "length").unwrap().has_alignment_override());
+
+        let attribute_packed = get("AttributePacked");
+        assert_eq!(attribute_packed.pack(), None);
+        assert!(attribute_packed.traits().is_packed());
+        assert!(attribute_packed.get_field(&types, "length").unwrap().has_alignment_override());
+
+        let packed_aligned = get("PackedAligned");
+        assert_eq!(packed_aligned.alignment(), 8);
+        assert_eq!(packed_aligned.pack(), Some(1));
+
+        let TypeKind::Union(packed_union) = types.get("PackedUnion").unwrap() else {
+            panic!("Expected Union type");
+        };
+        assert_eq!(packed_union.pack(), Some(1));
+        assert_eq!(packed_union.size(), 4);
+    }
+
+    #[test]
+    fn test_completeness() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let mut types = crawler.parse_file("tests/struct/completeness.h").unwrap();
+        let get = |types: &type_crawler::Types, name: &str| {
+            types.get(name).unwrap().as_struct(types).unwrap().completeness()
+        };
+
+        assert_eq!(get(&types, "Opaque"), Completeness::ForwardDeclared);
+        assert!(types.get("Opaque").unwrap().is_forward_decl());
+        assert_eq!(get(&types, "Tag"), Completeness::Empty);
+        assert!(!types.get("Tag").unwrap().is_forward_decl());
+        assert_eq!(get(&types, "Full"), Completeness::Complete);
+
+        let defs = crawler.parse_file("tests/struct/completeness_defs.h").unwrap();
+        assert_eq!(get(&defs, "Tag"), Completeness::ForwardDeclared);
+        types.extend(defs).unwrap();
+
+        // Definitions replace forward declarations, but forward declarations don't replace
+        // empty definitions
+        assert_eq!(get(&types, "Opaque"), Completeness::Complete);
+        assert_eq!(get(&types, "Tag"), Completeness::Empty);
+    }
+
+    #[test]
+    fn test_anonymous_members() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/anonymous.h").unwrap();
+
+        let register = types.get("Register").unwrap().as_struct(&types).unwrap();
+        assert_eq!(register.get_field(&types, "control").unwrap().offset_bytes(), 0);
+        assert_eq!(register.get_field(&types, "raw").unwrap().offset_bytes(), 4);
+        assert_eq!(register.get_field(&types, "low").unwrap().offset_bytes(), 4);
+        assert_eq!(register.get_field(&types, "high").unwrap().offset_bytes(), 6);
+        assert_eq!(register.get_field(&types, "high").unwrap().kind(), &TypeKind::U16);
+        assert_eq!(register.get_field(&types, "mode").unwrap().offset_bytes(), 8);
+        assert_eq!(register.get_field(&types, "flags").unwrap().offset_bytes(), 9);
+        assert_eq!(register.get_field(&types, "bits").unwrap().offset_bytes(), 9);
+        assert_eq!(register.get_field(&types, "named").unwrap().offset_bytes(), 12);
+        // Members of named fields are not accessible directly
+        assert!(register.get_field(&types, "x").is_none());
+
+        let TypeKind::Union(word) = types.get("Word").unwrap() else {
+            panic!("Expected Union type");
+        };
+        assert_eq!(word.get_field(&types, "value").unwrap().offset_bytes(), 0);
+        assert_eq!(word.get_field(&types, "lo").unwrap().offset_bytes(), 0);
+        assert_eq!(word.get_field(&types, "hi").unwrap().offset_bytes(), 4);
+        assert!(word.get_field(&types, "missing").is_none());
+    }
+
+    #[test]
+    fn test_bitfield_units() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/bitfield_units.h").unwrap();
+        let flags = types.get("Flags").unwrap().as_struct(&types).unwrap();
+
+        assert!(flags.get_field(&types, "kind").unwrap().bit_field_unit().is_none());
+
+        let a = flags.get_field(&types, "a").unwrap();
+        assert_eq!(a.offset_bits(), 8);
+        assert_eq!(a.kind(), &TypeKind::U16);
+        let a_unit = a.bit_field_unit().unwrap();
+        assert_eq!(a_unit.offset(), 0);
+        assert_eq!(a_unit.size(), 2);
+        assert_eq!(a_unit.bit_position(), 8);
+        assert_eq!(a_unit.mask(), 0xf00);
+
+        // Doesn't fit in the remaining bits of the first unit
+        let b_unit = *flags.get_field(&types, "b").unwrap().bit_field_unit().unwrap();
+        assert_eq!(b_unit.offset(), 2);
+        assert_eq!(b_unit.size(), 2);
+        assert_eq!(b_unit.bit_position(), 0);
+
+        let c = flags.get_field(&types, "c").unwrap();
+        assert_eq!(c.offset_bits(), 22);
+        let c_unit = c.bit_field_unit().unwrap();
+        assert_eq!(c_unit.offset(), 0);
+        assert_eq!(c_unit.size(), 4);
+        assert_eq!(c_unit.bit_position(), 22);
+        assert_eq!(c_unit.mask(), 0b111 << 22);
+        assert_eq!(c.size(&types), 4);
+
+        let crawler = TypeCrawler::new(Env::new(EnvOptions {
+            endianness: Endianness::Big,
+            ..EnvOptions::default()
+        }))
+        .unwrap();
+        let types = crawler.parse_file("tests/struct/bitfield_units.h").unwrap();
+        let flags = types.get("Flags").unwrap().as_struct(&types).unwrap();
+        // Only the reported bit positions depend on the byte order
+        let a = flags.get_field(&types, "a").unwrap();
+        assert_eq!(a.offset_bits(), 8);
+        let a_unit = *a.bit_field_unit().unwrap();
+        assert_eq!(a_unit.offset(), 0);
+        assert_eq!(a_unit.size(), 2);
+        assert_eq!(a_unit.bit_position(), 4);
+        assert_eq!(a_unit.mask(), 0xf0);
+        let c_unit = *flags.get_field(&types, "c").unwrap().bit_field_unit().unwrap();
+        assert_eq!(c_unit.bit_position(), 7);
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/struct/doc_comments.h").unwrap();
+
+        let save_data = types.get("SaveData").unwrap();
+        let comment = save_data.comment().unwrap();
+        assert!(comment.raw().starts_with("/**"));
+        assert_eq!(comment.brief(), Some("Player state saved to the memory card."));
+        assert_eq!(
+            comment.text(),
+            "Player state saved to the memory card.\n\nWritten once per frame."
+        );
+
+        let TypeKind::Struct(save_data) = save_data else {
+            panic!("Expected Struct type");
+        };
+        let fields = save_data.fields();
+        assert_eq!(fields[0].comment().unwrap().text(), "Current health points");
+        assert_eq!(fields[1].comment().unwrap().text(), "Current magic points");
+        assert!(fields[2].comment().is_none());
+
+        let actor_kind = types.get_enum("ActorKind").unwrap();
+        assert_eq!(actor_kind.comment().unwrap().brief(), Some("Actor categories"));
+        let constants = actor_kind.constants();
+        assert_eq!(constants[0].comment().unwrap().brief(), Some("Controlled by the player"));
+        assert_eq!(constants[1].comment().unwrap().text(), "Hostile");
+
+        let frame_count = types.get("FrameCount").unwrap();
+        assert_eq!(frame_count.comment().unwrap().raw(), "/// Frame counter");
+    }
+
+    #[test]
+    fn test_offset_comments() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let path = "tests/struct/offset_comments.h";
+        assert!(crawler.parse_file(path).is_err());
+
+        let mismatches =
+            crawler.check_offset_comments(path, &OffsetCommentOptions::default()).unwrap();
+        assert_eq!(mismatches.len(), 4);
+
+        assert_eq!(mismatches[0].record(), "Actor");
+        assert_eq!(mismatches[0].field(), Some("flags"));
+        assert_eq!(mismatches[0].expected(), 0x8);
+        assert_eq!(mismatches[0].found(), 0xc);
+        assert_eq!(mismatches[0].location().line(), 4);
+
+        assert_eq!(mismatches[1].field(), Some("energy"));
+        assert_eq!(mismatches[1].expected(), 0xc);
+        assert_eq!(mismatches[1].found(), 0xd);
+
+        // Line comment on the line above the field
+        assert_eq!(mismatches[2].field(), Some("timer"));
+        assert_eq!(mismatches[2].expected(), 0x18);
+        assert_eq!(mismatches[2].found(), 0x20);
+        assert_eq!(mismatches[2].location().line(), 11);
+
+        assert_eq!(mismatches[3].record(), "Anonymous");
+        assert_eq!(mismatches[3].field(), Some("b"));
+        assert_eq!(mismatches[3].expected(), 0x4);
+        assert_eq!(mismatches[3].found(), 0x2);
+        assert_eq!(mismatches[3].location().line(), 21);
+
+        let mismatches = crawler
+            .check_offset_comments(
+                path,
+                &OffsetCommentOptions { sizes: true, ..Default::default() },
+            )
+            .unwrap();
+        let sizes = mismatches.iter().filter(|m| m.field().is_none()).collect::<Vec<_>>();
+        assert_eq!(sizes.len(), 2);
+        assert_eq!(sizes[0].record(), "Actor");
+        assert_eq!(sizes[0].expected(), 0x20);
+        assert_eq!(sizes[0].found(), 0x28);
+        assert_eq!(sizes[1].record(), "Item");
+        assert_eq!(sizes[1].expected(), 0x8);
+        assert_eq!(sizes[1].location().line(), 17);
+        assert!(sizes[1].to_string().ends_with("Item has size 0x8, but its comment says 0x10"));
+    }
+
+    #[test]
+    fn test_rewrite_offset_comments() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let path = "tests/struct/offset_rewrite.h";
+
+        let options = OffsetCommentOptions { sizes: true, ..Default::default() };
+        let rewritten = crawler.rewrite_offset_comments(path, &options).unwrap();
+        assert_eq!(
+            rewritten,
+            r#"struct Player {
+    /* 0x00 */ int id;
+    /* 0x04 */ short x;
+    /* 0x06 */ short y; // y position
+    /* 0x08 */ char flags;
+    /* 0x0C */ union {
+        /* 0x0C */ int hp;
+        /* 0x0C */ float energy;
+    };
+}; /* size 0x10 */ // player state
+
+typedef struct Pair {
+    /* 0x00 */ int a;
+    /* 0x08 */ long long b;
+    // 0x10
+    int c;
+} Pair; /* size 0x18 */
+"#
+        );
+
+        let options = OffsetCommentOptions {
+            sizes: true,
+            hex_width: 4,
+            uppercase_hex: false,
+            offset_comment_style: CommentStyle::Block,
+            size_comment_style: CommentStyle::Line,
+        };
+        let rewritten = crawler.rewrite_offset_comments(path, &options).unwrap();
+        assert_eq!(
+            rewritten,
+            r#"struct Player {
+    /* 0x0000 */ int id;
+    /* 0x0004 */ short x;
+    /* 0x0006 */ short y; // y position
+    /* 0x0008 */ char flags;
+    /* 0x000c */ union {
+        /* 0x000c */ int hp;
+        /* 0x000c */ float energy;
+    };
+}; /* size 0x0010 */ // player state
+
+typedef struct Pair {
+    /* 0x0000 */ int a;
+    /* 0x0008 */ long long b;
+    // 0x0010
+    int c;
+} Pair; // size = 0x0018
+"#
+        );
+
+        let rewritten =
+            crawler.rewrite_offset_comments(path, &OffsetCommentOptions::default()).unwrap();
+        assert!(rewritten.contains("}; // player state\n"));
+        assert!(rewritten.contains("} Pair;\n"));
+
+        // Inserted offset comments go on the line above the field
+        let options =
+            OffsetCommentOptions { offset_comment_style: CommentStyle::Line, ..Default::default() };
+        let rewritten = crawler.rewrite_offset_comments(path, &options).unwrap();
+        assert_eq!(
+            rewritten,
+            r#"struct Player {
+    // 0x00
+    int id;
+    /* 0x04 */ short x;
+    // 0x06
+    short y; // y position
+    /* 0x08 */ char flags;
+    // 0x0C
+    union {
+        // 0x0C
+        int hp;
+        // 0x0C
+        float energy;
+    };
+}; // player state
+
+typedef struct Pair {
+    // 0x00
+    int a;
+    /* 0x08 */ long long b;
+    // 0x10
+    int c;
+} Pair;
+"#
+        );
+    }
 }
@@ -29,11 +29,34 @@ mod tests {
             panic!("Expected Union type");
         };
         assert_eq!(bitfield.fields().len(), 3);
+        assert_eq!(bitfield.fields()[0].offset_bits(), 0);
         assert_eq!(bitfield.fields()[0].bit_field_width(), Some(3));
-        assert_eq!(bitfield.fields()[0].size(&types), 1);
+        assert_eq!(bitfield.fields()[0].size(&types), 4);
         assert_eq!(bitfield.fields()[1].bit_field_width(), Some(5));
-        assert_eq!(bitfield.fields()[1].size(&types), 1);
+        assert_eq!(bitfield.fields()[1].size(&types), 4);
         assert_eq!(bitfield.fields()[2].bit_field_width(), Some(2));
-        assert_eq!(bitfield.fields()[2].size(&types), 1);
+        assert_eq!(bitfield.fields()[2].size(&types), 4);
+    }
+
+    #[test]
+    fn test_members() {
+        let crawler = TypeCrawler::new(Env::new(EnvOptions::default())).unwrap();
+        let types = crawler.parse_file("tests/union/members.h").unwrap();
+
+        let TypeKind::Union(packet) = types.get("Packet").unwrap() else {
+            panic!("Expected Union type");
+        };
+        assert_eq!(packet.fields().len(), 3);
+        assert!(packet.fields().iter().all(|f| f.offset_bits() == 0));
+        assert_eq!(packet.fields()[2].alignment(), 1);
+        assert_eq!(packet.get_field(&types, "high").unwrap().offset_bits(), 16);
+        assert_eq!(packet.get_field(&types, "rest").unwrap().offset_bits(), 20);
+
+        // Structs and unions can be handled alike through `RecordFields`
+        let records = ["Packet", "Message"].map(|name| types.get(name).unwrap());
+        let field_counts = records.map(|record| record.as_record(&types).unwrap().fields().len());
+        assert_eq!(field_counts, [3, 2]);
+        let message = types.get("Message").unwrap().as_record(&types).unwrap();
+        assert_eq!(message.get_field(&types, "packet").unwrap().offset_bytes(), 4);
     }
 }
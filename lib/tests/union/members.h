@@ -0,0 +1,14 @@
+typedef union {
+    unsigned int raw;
+    struct {
+        unsigned short low;
+        unsigned short high : 4;
+        unsigned short rest : 12;
+    };
+    unsigned char bytes[4];
+} Packet;
+
+typedef struct {
+    int id;
+    Packet packet;
+} Message;